@@ -0,0 +1,445 @@
+use aoc2018::{
+    answers::{self, Answers, Verdict},
+    combat::{self, Battle, Rules},
+    days::{self, day13, day15, day17, Day},
+    debugger::Debugger,
+    disasm,
+    elfcode::{Machine, Program},
+    frames::Recorder,
+    input::{self, Source},
+    json,
+    timing::{self, Timing},
+};
+use camino::Utf8PathBuf;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufWriter},
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc2018", about = "Advent of Code 2018 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Debug, Subcommand)]
+enum Cmd {
+    /// Solve one day, or every registered day with --all
+    Run {
+        #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+        /// Only run this part (1 or 2)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+        #[arg(long, conflicts_with = "input")]
+        all: bool,
+        /// Report how long parsing and each part took
+        #[arg(long)]
+        time: bool,
+        /// Repeat parsing and each part N times and report min/mean/max (implies --time)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        bench: Option<u32>,
+        /// `json` prints one JSON object per line for every phase, timings included
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Check answers against the known-good answers file
+    Verify {
+        /// Only verify this day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// TOML file of known-good answers
+        #[arg(long, default_value = answers::DEFAULT_PATH)]
+        answers: Utf8PathBuf,
+        /// Directory holding `dayNN.txt` inputs
+        #[arg(long, default_value = input::DEFAULT_DIR)]
+        inputs_dir: Utf8PathBuf,
+    },
+    /// Disassemble an ElfCode program such as the day 19 input
+    Disasm {
+        /// Take the program from this day's input
+        #[arg(short, long, default_value_t = 19)]
+        day: u8,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Print structured C-like pseudo-code instead of a listing
+        #[arg(long)]
+        pseudo: bool,
+    },
+    /// Step through an ElfCode program with breakpoints and watchpoints
+    Debug {
+        /// Take the program from this day's input
+        #[arg(short, long, default_value_t = 19)]
+        day: u8,
+        #[command(flatten)]
+        input: InputArgs,
+        #[arg(long, default_value_t = 6)]
+        registers: usize,
+        /// Initial value of register 0
+        #[arg(long, default_value_t = 0)]
+        r0: usize,
+        /// Run debugger commands from this file instead of reading them from stdin
+        #[arg(long)]
+        script: Option<Utf8PathBuf>,
+        /// Write every executed instruction to this file
+        #[arg(long)]
+        trace: Option<Utf8PathBuf>,
+        /// Stop tracing after this many steps
+        #[arg(long, default_value_t = 100_000, requires = "trace")]
+        trace_limit: u64,
+    },
+    /// Record a simulation frame by frame: day 13 carts, 15 combat or 17 water
+    Record {
+        #[arg(short, long)]
+        day: u8,
+        #[command(flatten)]
+        input: InputArgs,
+        #[arg(long, value_enum, default_value_t = FrameFormat::Ascii)]
+        format: FrameFormat,
+        /// File to write, or a directory of numbered images for `ppm`
+        #[arg(short, long)]
+        output: Utf8PathBuf,
+        /// Keep only every Nth frame
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        every: usize,
+        /// Pixels per tile in `ppm` images
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+        /// Seconds between frames in `cast` recordings
+        #[arg(long, default_value_t = 0.1)]
+        delay: f64,
+    },
+    /// Fight a day 15 battle under changed rules
+    Combat {
+        #[command(flatten)]
+        input: InputArgs,
+        /// TOML file of faction HP and attack and whether units move diagonally
+        #[arg(long)]
+        rules: Option<Utf8PathBuf>,
+        /// Find the lowest attack with which this faction loses nobody
+        #[arg(long, value_name = "FACTION")]
+        min_attack: Option<char>,
+        /// Write every move, attack and death to this file, for `replay`
+        #[arg(long, conflicts_with = "min_attack")]
+        log: Option<Utf8PathBuf>,
+    },
+    /// Rebuild a day 15 map from a combat log
+    Replay {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Log written by `combat --log`
+        #[arg(long)]
+        log: Utf8PathBuf,
+        /// Show the map after this many full rounds, or at the end
+        #[arg(short, long)]
+        round: Option<i32>,
+    },
+    /// List the registered days
+    List,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FrameFormat {
+    /// Plain text frames one after another
+    Ascii,
+    /// An asciinema v2 recording
+    Cast,
+    /// One PPM image per frame
+    Ppm,
+}
+
+/// Where a command reads its puzzle input from.
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Read the input from this file, or from stdin with `-`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding `dayNN.txt` inputs
+    #[arg(long, default_value = input::DEFAULT_DIR)]
+    inputs_dir: Utf8PathBuf,
+}
+
+impl InputArgs {
+    fn source(&self) -> Source {
+        match &self.input {
+            Some(arg) => Source::from_arg(arg),
+            None => Source::Dir(self.inputs_dir.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RunOpts {
+    part: Option<u8>,
+    time: bool,
+    runs: u32,
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl Phase {
+    /// Stable name for machine-readable output.
+    fn key(self) -> String {
+        match self {
+            Phase::Parse => "parse".to_string(),
+            Phase::Part(p) => format!("part{}", p),
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(p) => write!(f, "part {}", p),
+        }
+    }
+}
+
+fn report(day: &Day, phase: Phase, answer: Option<&str>, timing: Timing, opts: &RunOpts) {
+    match opts.format {
+        Format::Json => println!(
+            "{{\"day\":{},\"phase\":{},\"answer\":{},\"runs\":{},\"min_ns\":{},\"mean_ns\":{},\"max_ns\":{}}}",
+            day.day,
+            json::string(&phase.key()),
+            answer.map_or("null".to_string(), json::string),
+            timing.runs,
+            timing.min.as_nanos(),
+            timing.mean.as_nanos(),
+            timing.max.as_nanos(),
+        ),
+        Format::Text => {
+            let suffix = if opts.time { format!(" ({})", timing) } else { String::new() };
+            match answer {
+                None if opts.time => println!("day {} {}: {}", day.day, phase, timing),
+                None => {}
+                Some(answer) if answer.contains('\n') => {
+                    println!("day {} {}:{}\n{}", day.day, phase, suffix, answer.trim_end())
+                }
+                Some(answer) => println!("day {} {}: {}{}", day.day, phase, answer, suffix),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    pass: usize,
+    fail: usize,
+    missing: usize,
+}
+
+fn verify_day(day: &Day, answers: &Answers, source: &Source, tally: &mut Tally) {
+    let input = match input::load(day, source) {
+        Ok(input) => input,
+        _ => {
+            println!("day {}: missing input", day.day);
+            tally.missing += 2;
+            return;
+        }
+    };
+    let solution = match (day.parse)(&input) {
+        Ok(solution) => solution,
+        Err(e) => {
+            println!("day {}: FAIL, could not parse the input: {:#}", day.day, e);
+            tally.fail += 2;
+            return;
+        }
+    };
+    for p in [1, 2] {
+        let answer = match p {
+            1 => solution.part1(),
+            _ => solution.part2(),
+        };
+        let verdict = answers.check(day.day, p, &answer);
+        match verdict {
+            Verdict::Pass => tally.pass += 1,
+            Verdict::Fail { .. } => tally.fail += 1,
+            Verdict::Missing => tally.missing += 1,
+        }
+        println!("day {} part {}: {}", day.day, p, verdict);
+    }
+}
+
+fn run_day(day: &Day, opts: &RunOpts, input: &str) -> Result<()> {
+    let (solution, timing) = timing::measure(opts.runs, || (day.parse)(input));
+    let solution = solution.wrap_err_with(|| format!("failed to parse the day {} input", day.day))?;
+    report(day, Phase::Parse, None, timing, opts);
+    let parts = match opts.part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    for p in parts {
+        let (answer, timing) = timing::measure(opts.runs, || match p {
+            1 => solution.part1(),
+            _ => solution.part2(),
+        });
+        report(day, Phase::Part(p), Some(&answer.to_string()), timing, opts);
+    }
+    Ok(())
+}
+
+fn registered(day: u8) -> Result<&'static Day> {
+    days::get(day).ok_or_else(|| eyre!("day {} is not registered", day))
+}
+
+fn load_input(day: &Day, input: &InputArgs) -> Result<String> {
+    input::load(day, &input.source())
+}
+
+fn load_program(day: u8, input: &InputArgs) -> Result<Program> {
+    Program::parse(&load_input(registered(day)?, input)?).wrap_err("not an ElfCode program")
+}
+
+/// Runs debugger commands from `script`, stopping at the first error, or
+/// interactively from stdin, where errors are only reported.
+fn debug_session(debugger: &mut Debugger, script: Option<&Utf8PathBuf>) -> Result<()> {
+    let Some(path) = script else {
+        eprint!("(elf) ");
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            if matches!(line.trim(), "q" | "quit") {
+                break;
+            }
+            match debugger.command(&line) {
+                Ok(out) if out.is_empty() => {}
+                Ok(out) => println!("{}", out),
+                Err(e) => println!("error: {:#}", e),
+            }
+            eprint!("(elf) ");
+        }
+        return Ok(());
+    };
+    let script = std::fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path))?;
+    for (n, line) in script.lines().enumerate() {
+        let line = line.split('#').next().unwrap();
+        let out = debugger.command(line).wrap_err_with(|| format!("{} line {}", path, n + 1))?;
+        if !out.is_empty() {
+            println!("{}", out);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let cli = Cli::parse();
+
+    match cli.cmd {
+        Cmd::List => {
+            for day in days::DAYS {
+                println!("{:>2}  {}", day.day, day.title);
+            }
+        }
+        Cmd::Verify { day, answers: path, inputs_dir } => {
+            let answers = Answers::load(&path)?;
+            let source = Source::Dir(inputs_dir);
+            let selected: Vec<&Day> = match day {
+                Some(n) => vec![registered(n)?],
+                None => days::DAYS.iter().collect(),
+            };
+            let mut tally = Tally::default();
+            for day in selected {
+                verify_day(day, &answers, &source, &mut tally);
+            }
+            println!("{} passed, {} failed, {} missing", tally.pass, tally.fail, tally.missing);
+            if tally.fail > 0 {
+                return Err(eyre!("{} answers did not match {}", tally.fail, path));
+            }
+        }
+        Cmd::Disasm { day, input, pseudo } => {
+            let program = load_program(day, &input)?;
+            match pseudo {
+                true => print!("{}", disasm::pseudo_code(&program)),
+                false => print!("{}", disasm::listing(&program)),
+            }
+        }
+        Cmd::Debug { day, input, registers, r0, script, trace, trace_limit } => {
+            let mut machine = Machine::new(load_program(day, &input)?, registers)?;
+            machine.regs[0] = r0;
+            let mut debugger = Debugger::new(machine);
+            if let Some(path) = &trace {
+                let file = File::create(path).wrap_err_with(|| format!("failed to create {}", path))?;
+                debugger.trace_to(BufWriter::new(file), trace_limit);
+            }
+            debug_session(&mut debugger, script.as_ref())?;
+        }
+        Cmd::Record { day, input, format, output, every, scale, delay } => {
+            let record = match day {
+                13 => day13::record,
+                15 => day15::record,
+                17 => day17::record,
+                _ => return Err(eyre!("day {} has no recordable simulation; try 13, 15 or 17", day)),
+            };
+            let input = load_input(registered(day)?, &input)?;
+            let create = || File::create(&output).map(BufWriter::new).wrap_err_with(|| format!("failed to create {}", output));
+            let mut recorder = match format {
+                FrameFormat::Ascii => Recorder::ascii(create()?),
+                FrameFormat::Cast => Recorder::asciicast(create()?, delay),
+                FrameFormat::Ppm => Recorder::ppm(output.clone(), scale),
+            }
+            .every(every);
+            let frames = record(&input, &mut recorder)?;
+            println!("wrote {} frames to {}", frames, output);
+        }
+        Cmd::Combat { input, rules, min_attack, log } => {
+            let rules = match rules {
+                Some(path) => Rules::load(&path)?,
+                None => Rules::default(),
+            };
+            let battle = Battle::new(&load_input(registered(15)?, &input)?, &rules)?;
+            match min_attack {
+                Some(faction) => match battle.min_attack(faction)? {
+                    Some((attack, outcome)) => println!("{} needs {} attack: {}", faction, attack, outcome),
+                    None => println!("{} loses a unit at any attack", faction),
+                },
+                None => {
+                    let mut events = String::new();
+                    println!("{}", battle.fight_with(|e| events += &format!("{}\n", e)));
+                    if let Some(path) = log {
+                        std::fs::write(&path, events).wrap_err_with(|| format!("failed to write {}", path))?;
+                    }
+                }
+            }
+        }
+        Cmd::Replay { input, log, round } => {
+            let map = load_input(registered(15)?, &input)?;
+            let events = std::fs::read_to_string(&log).wrap_err_with(|| format!("failed to read {}", log))?;
+            print!("{}", combat::replay(&map, &events, round.unwrap_or(i32::MAX)).wrap_err_with(|| format!("invalid log {}", log))?);
+        }
+        Cmd::Run { day, part, input, all, time, bench, format } => {
+            let opts = RunOpts { part, time: time || bench.is_some(), runs: bench.unwrap_or(1), format };
+            if all {
+                for day in days::DAYS {
+                    if let Err(e) = load_input(day, &input).and_then(|input| run_day(day, &opts, &input)) {
+                        match format {
+                            Format::Text => println!("day {}: skipped, {:#}", day.day, e),
+                            Format::Json => eprintln!("day {}: skipped, {:#}", day.day, e),
+                        }
+                    }
+                }
+            } else {
+                let day = registered(day.unwrap())?;
+                run_day(day, &opts, &load_input(day, &input)?)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,5 +0,0 @@
-
-
-fn main() {
-    //   println!("{}", part1());
-   }
\ No newline at end of file
@@ -0,0 +1,111 @@
+use crate::solution::Answer;
+use camino::Utf8Path;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::{collections::BTreeMap, fmt};
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Known-good answers, keyed by day and part. The file is a table per day:
+///
+/// ```toml
+/// [day07]
+/// part1 = "CHILFNMORYKGAQXUVBZPSJWDET"
+/// part2 = 891
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, got: String },
+    /// No known-good answer recorded for this part.
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, got } if expected.contains('\n') || got.contains('\n') => {
+                write!(f, "FAIL\nexpected:\n{}\ngot:\n{}", expected, got)
+            }
+            Verdict::Fail { expected, got } => write!(f, "FAIL, expected {}, got {}", expected, got),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path))?;
+        Self::parse(&text).wrap_err_with(|| format!("invalid answers file {}", path))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let table: toml::Table = text.parse()?;
+        let mut answers = BTreeMap::new();
+        for (key, parts) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| eyre!("`{}` is not a day, expected `dayNN`", key))?;
+            let parts = parts.as_table().ok_or_else(|| eyre!("`{}` must be a table", key))?;
+            for (name, value) in parts {
+                let part = match name.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(eyre!("`{}.{}`: expected `part1` or `part2`", key, name)),
+                };
+                let value = match value {
+                    toml::Value::String(s) => s.trim_end().to_string(),
+                    toml::Value::Integer(n) => n.to_string(),
+                    _ => return Err(eyre!("`{}.{}` must be a string or an integer", key, name)),
+                };
+                answers.insert((day, part), value);
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) => {
+                let got = answer.to_string().trim_end().to_string();
+                if got == expected {
+                    Verdict::Pass
+                } else {
+                    Verdict::Fail { expected: expected.to_string(), got }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[day07]\npart1 = \"ABC\"\npart2 = 891\n").unwrap();
+        assert_eq!(answers.check(7, 1, &"ABC".into()), Verdict::Pass);
+        assert_eq!(answers.check(7, 2, &891.into()), Verdict::Pass);
+        assert_eq!(
+            answers.check(7, 2, &890.into()),
+            Verdict::Fail { expected: "891".into(), got: "890".into() }
+        );
+        assert_eq!(answers.check(8, 1, &1.into()), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_bad_key() {
+        assert!(Answers::parse("[seven]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day07]\npart3 = 1\n").is_err());
+    }
+}
@@ -0,0 +1,663 @@
+use crate::{frames::Snapshot, geometry::Point, grid::Grid, parse};
+use camino::Utf8Path;
+use color_eyre::eyre::{ensure, eyre, Result, WrapErr};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt,
+};
+
+pub const WALL: char = '#';
+pub const OPEN: char = '.';
+
+/// One side of a fight, drawn on the map as `symbol`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Faction {
+    pub symbol: char,
+    pub hp: i32,
+    pub attack: i32,
+}
+
+impl Faction {
+    pub const fn new(symbol: char) -> Self {
+        Self { symbol, hp: 200, attack: 3 }
+    }
+}
+
+/// How a fight is fought. Every faction is the enemy of every other. Rules
+/// files change the puzzle's goblins and elves or add factions:
+///
+/// ```toml
+/// diagonal = true
+///
+/// [G]
+/// hp = 250
+///
+/// [O]
+/// attack = 5
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub factions: Vec<Faction>,
+    /// Units also move and attack diagonally.
+    pub diagonal: bool,
+}
+
+/// The puzzle's rules: goblins and elves with 200 HP and 3 attack.
+impl Default for Rules {
+    fn default() -> Self {
+        Self { factions: vec![Faction::new('G'), Faction::new('E')], diagonal: false }
+    }
+}
+
+impl Rules {
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path))?;
+        Self::parse(&text).wrap_err_with(|| format!("invalid rules file {}", path))
+    }
+
+    /// Reads changes to the default rules; factions not mentioned keep
+    /// their defaults and new ones start with 200 HP and 3 attack.
+    pub fn parse(text: &str) -> Result<Self> {
+        let table: toml::Table = text.parse()?;
+        let mut rules = Rules::default();
+        for (key, value) in table {
+            if key == "diagonal" {
+                rules.diagonal = value.as_bool().ok_or_else(|| eyre!("`diagonal` must be true or false"))?;
+                continue;
+            }
+            let symbol = match key.chars().exactly_one() {
+                Ok(c) if c != WALL && c != OPEN => c,
+                _ => return Err(eyre!("`{}` is not a faction, expected one map character other than `#` and `.`", key)),
+            };
+            let fields = value.as_table().ok_or_else(|| eyre!("`{}` must be a table", key))?;
+            for (name, value) in fields {
+                let n = value
+                    .as_integer()
+                    .and_then(|n| i32::try_from(n).ok())
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| eyre!("`{}.{}` must be a positive integer", key, name))?;
+                let faction = rules.faction_mut(symbol);
+                match name.as_str() {
+                    "hp" => faction.hp = n,
+                    "attack" => faction.attack = n,
+                    _ => return Err(eyre!("`{}.{}`: expected `hp` or `attack`", key, name)),
+                }
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn faction(&self, symbol: char) -> Option<&Faction> {
+        self.factions.iter().find(|f| f.symbol == symbol)
+    }
+
+    fn faction_mut(&mut self, symbol: char) -> &mut Faction {
+        match self.factions.iter().position(|f| f.symbol == symbol) {
+            Some(i) => &mut self.factions[i],
+            None => {
+                self.factions.push(Faction::new(symbol));
+                self.factions.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Unit {
+    faction: char,
+    pos: Point,
+    hp: i32,
+    attack: i32,
+}
+
+impl Unit {
+    fn alive(&self) -> bool {
+        self.hp > 0
+    }
+}
+
+/// How a fight ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// Full rounds completed.
+    pub rounds: i32,
+    pub remaining_hp: i32,
+    /// The faction left standing; `None` when the fight was cut short by a
+    /// protected faction's death or a stalemate.
+    pub winner: Option<char>,
+    pub deaths: BTreeMap<char, usize>,
+}
+
+impl Outcome {
+    pub fn score(&self) -> i32 {
+        self.rounds * self.remaining_hp
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.winner {
+            Some(w) => write!(f, "{} wins", w)?,
+            None => write!(f, "no winner")?,
+        }
+        write!(f, " after {} full rounds with {} HP left, outcome {}", self.rounds, self.remaining_hp, self.score())?;
+        for (faction, n) in &self.deaths {
+            write!(f, "; {} lost {}", faction, n)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something that happened in a fight. Each has a one-line text form that
+/// [`Event::parse`] reads back, so logs can be written out and replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Moved { faction: char, from: Point, to: Point },
+    Attacked { faction: char, at: Point, enemy: char, target: Point, damage: i32, hp_left: i32 },
+    Killed { faction: char, at: Point },
+    RoundComplete { round: i32 },
+    BattleEnded { rounds: i32, winner: Option<char> },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Moved { faction, from, to } => write!(f, "{} moves {} -> {}", faction, from, to),
+            Event::Attacked { faction, at, enemy, target, damage, hp_left } => {
+                write!(f, "{} at {} hits {} at {} for {} damage, {} HP left", faction, at, enemy, target, damage, hp_left)
+            }
+            Event::Killed { faction, at } => write!(f, "{} at {} dies", faction, at),
+            Event::RoundComplete { round } => write!(f, "round {} complete", round),
+            Event::BattleEnded { rounds, winner: Some(w) } => write!(f, "battle over after {} full rounds, {} wins", rounds, w),
+            Event::BattleEnded { rounds, winner: None } => write!(f, "battle over after {} full rounds, no winner", rounds),
+        }
+    }
+}
+
+impl Event {
+    pub fn parse(line: &str) -> Result<Self> {
+        let faction = |w: &str| w.chars().exactly_one().map_err(|_| parse::error_at(line, w, "expected a faction"));
+        let point = |w: &str| -> Result<Point> {
+            let (x, y) = parse::split_once(line, w, ",")?;
+            Ok(Point::new(parse::number(line, x)?, parse::number(line, y)?))
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            [f, "moves", from, "->", to] => Ok(Event::Moved { faction: faction(f)?, from: point(from)?, to: point(to)? }),
+            [f, "at", at, "hits", e, "at", target, "for", damage, "damage,", hp_left, "HP", "left"] => Ok(Event::Attacked {
+                faction: faction(f)?,
+                at: point(at)?,
+                enemy: faction(e)?,
+                target: point(target)?,
+                damage: parse::number(line, damage)?,
+                hp_left: parse::number(line, hp_left)?,
+            }),
+            [f, "at", at, "dies"] => Ok(Event::Killed { faction: faction(f)?, at: point(at)? }),
+            ["round", n, "complete"] => Ok(Event::RoundComplete { round: parse::number(line, n)? }),
+            ["battle", "over", "after", n, "full", "rounds,", "no", "winner"] => {
+                Ok(Event::BattleEnded { rounds: parse::number(line, n)?, winner: None })
+            }
+            ["battle", "over", "after", n, "full", "rounds,", w, "wins"] => {
+                Ok(Event::BattleEnded { rounds: parse::number(line, n)?, winner: Some(faction(w)?) })
+            }
+            _ => Err(parse::error_at(line, line.trim(), "not a combat event")),
+        }
+    }
+}
+
+/// Rebuilds the map of the fight that started on `map` and produced `log`,
+/// as it was after `rounds` full rounds, or at the end if the fight ended
+/// sooner. Every event is checked against the map so far.
+pub fn replay(map: &str, log: &str, rounds: i32) -> Result<Grid<char>> {
+    let mut field = Grid::parse(map.trim_end(), |c| !c.is_whitespace(), "map square")?;
+    let events = parse::lines(log, Event::parse)?;
+    let expect = |field: &Grid<char>, p: Point, c: char| match field.at(p) {
+        Some(&found) if found == c => Ok(()),
+        found => Err(eyre!("expected `{}` at {}, found {:?}", c, p, found)),
+    };
+    let mut done = 0;
+    for event in &events {
+        if done >= rounds { break }
+        match *event {
+            Event::Moved { faction, from, to } => {
+                expect(&field, from, faction).and_then(|_| expect(&field, to, OPEN))
+                    .wrap_err_with(|| format!("cannot replay `{}`", event))?;
+                field[from] = OPEN;
+                field[to] = faction;
+            }
+            Event::Attacked { faction, at, enemy, target, .. } => {
+                expect(&field, at, faction).and_then(|_| expect(&field, target, enemy))
+                    .wrap_err_with(|| format!("cannot replay `{}`", event))?;
+            }
+            Event::Killed { faction, at } => {
+                expect(&field, at, faction).wrap_err_with(|| format!("cannot replay `{}`", event))?;
+                field[at] = OPEN;
+            }
+            Event::RoundComplete { round } => {
+                ensure!(round == done + 1, "round {} complete after round {}", round, done);
+                done = round;
+            }
+            Event::BattleEnded { .. } => break,
+        }
+    }
+    Ok(field)
+}
+
+#[derive(Debug, Clone)]
+pub struct Battle {
+    field: Grid<char>,
+    /// Every unit in reading order as of the start of the round; the dead
+    /// stay in place, with no HP left, until the round is over.
+    units: Vec<Unit>,
+    diagonal: bool,
+    /// Full rounds completed.
+    rounds: i32,
+    deaths: BTreeMap<char, usize>,
+    /// Stop at the first death in this faction.
+    protected: Option<char>,
+    /// A whole round went by without anyone moving or attacking.
+    stalled: bool,
+}
+
+impl fmt::Display for Battle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.field)
+    }
+}
+
+impl Snapshot for Battle {
+    fn snapshot(&self) -> Grid<char> {
+        self.field.clone()
+    }
+}
+
+impl Battle {
+    /// Sets up the fight on `map`, where every faction of `rules` may
+    /// appear alongside walls and open squares.
+    pub fn new(map: &str, rules: &Rules) -> Result<Self> {
+        let allowed = |c| c == WALL || c == OPEN || rules.faction(c).is_some();
+        let field = Grid::parse(map.trim_end(), allowed, "wall, open square or faction")?;
+        let units = field
+            .iter()
+            .filter_map(|(p, &c)| rules.faction(c).map(|f| Unit { faction: c, pos: p.into(), hp: f.hp, attack: f.attack }))
+            .collect();
+        Ok(Battle { field, units, diagonal: rules.diagonal, rounds: 0, deaths: BTreeMap::new(), protected: None, stalled: false })
+    }
+
+    pub fn rounds(&self) -> i32 {
+        self.rounds
+    }
+
+    /// This battle with `faction`'s units hitting for `attack`.
+    pub fn with_attack(&self, faction: char, attack: i32) -> Battle {
+        let mut battle = self.clone();
+        battle.units.iter_mut().filter(|u| u.faction == faction).for_each(|u| u.attack = attack);
+        battle
+    }
+
+    /// This battle, ending at the first death in `faction`.
+    pub fn protecting(&self, faction: char) -> Battle {
+        Battle { protected: Some(faction), ..self.clone() }
+    }
+
+    /// Only one faction left, a protected unit down, or a stalemate.
+    pub fn over(&self) -> bool {
+        self.one_faction_left() || self.stalled || self.protected.is_some_and(|f| self.deaths.contains_key(&f))
+    }
+
+    fn one_faction_left(&self) -> bool {
+        self.units.iter().filter(|u| u.alive()).map(|u| u.faction).all_equal()
+    }
+
+    fn winner(&self) -> Option<char> {
+        match self.one_faction_left() {
+            true => self.units.iter().find(|u| u.alive()).map(|u| u.faction),
+            false => None,
+        }
+    }
+
+    pub fn fight(self) -> Outcome {
+        self.fight_with(|_| {})
+    }
+
+    /// [`Battle::fight`], passing every event to `log` as it happens.
+    pub fn fight_with(mut self, mut log: impl FnMut(Event)) -> Outcome {
+        while !self.over() {
+            self.round_with(&mut log);
+        }
+        let remaining_hp = self.units.iter().filter(|u| u.alive()).map(|u| u.hp).sum();
+        Outcome { rounds: self.rounds, remaining_hp, winner: self.winner(), deaths: self.deaths }
+    }
+
+    /// The lowest attack with which `faction` loses nobody, and how that
+    /// fight ends. Survival is not always monotonic in attack, so rather
+    /// than a binary search, attacks are tried in increasing batches, each
+    /// fought in parallel. Past the highest enemy HP every hit kills and
+    /// more attack cannot help, so `None` means no attack works. Fails when
+    /// `faction` has no units on the map.
+    pub fn min_attack(&self, faction: char) -> Result<Option<(i32, Outcome)>> {
+        let units = || self.units.iter();
+        let from = units()
+            .filter(|u| u.faction == faction)
+            .map(|u| u.attack)
+            .max()
+            .ok_or_else(|| eyre!("there are no `{}` units on the map", faction))?
+            + 1;
+        let to = units().filter(|u| u.faction != faction).map(|u| u.hp).max().unwrap_or(from).max(from);
+        let batch = rayon::current_num_threads().max(2);
+        Ok((from..=to).step_by(batch).find_map(|start| {
+            (start..(start + batch as i32).min(to + 1))
+                .into_par_iter()
+                .map(|attack| (attack, self.with_attack(faction, attack).protecting(faction).fight()))
+                .filter(|(_, outcome)| !outcome.deaths.contains_key(&faction))
+                .min_by_key(|&(attack, _)| attack)
+        }))
+    }
+
+    /// Runs one round, every living unit taking a turn in reading order.
+    /// The round only counts when no unit ran out of targets during it.
+    pub fn round(&mut self) {
+        self.round_with(|_| {});
+    }
+
+    /// [`Battle::round`], passing every event to `log` as it happens.
+    pub fn round_with(&mut self, mut log: impl FnMut(Event)) {
+        self.units.sort_by_key(|u| u.pos);
+        let mut complete = true;
+        let mut active = false;
+        for i in 0..self.units.len() {
+            if !self.units[i].alive() { continue }
+            if self.over() { complete = false; break }
+            active |= self.turn(i, &mut log);
+        }
+        self.units.retain(|u| u.alive());
+        if complete {
+            self.rounds += 1;
+            log(Event::RoundComplete { round: self.rounds });
+        }
+        self.stalled = !active && !self.over();
+        if self.over() {
+            log(Event::BattleEnded { rounds: self.rounds, winner: self.winner() });
+        }
+    }
+
+    /// Moves and attacks with unit `i`, returning whether it did either.
+    fn turn(&mut self, i: usize, log: &mut impl FnMut(Event)) -> bool {
+        let step = self.choose_step(i);
+        if let Some(step) = step {
+            let unit = &mut self.units[i];
+            log(Event::Moved { faction: unit.faction, from: unit.pos, to: step });
+            self.field[unit.pos] = OPEN;
+            self.field[step] = unit.faction;
+            unit.pos = step;
+        }
+        let target = self.target(i);
+        if let Some(j) = target {
+            let Unit { faction, pos: at, attack: damage, .. } = self.units[i];
+            let target = &mut self.units[j];
+            target.hp -= damage;
+            log(Event::Attacked { faction, at, enemy: target.faction, target: target.pos, damage, hp_left: target.hp.max(0) });
+            if !target.alive() {
+                log(Event::Killed { faction: target.faction, at: target.pos });
+                self.field[target.pos] = OPEN;
+                *self.deaths.entry(target.faction).or_default() += 1;
+            }
+        }
+        step.is_some() || target.is_some()
+    }
+
+    /// The squares next to `p` in reading order, diagonals included when
+    /// the rules allow.
+    fn around(&self, p: Point) -> Vec<Point> {
+        match self.diagonal {
+            true => p.neighbours8().to_vec(),
+            false => p.neighbours4().to_vec(),
+        }
+    }
+
+    fn is_open(&self, p: Point) -> bool {
+        self.field.at(p) == Some(&OPEN)
+    }
+
+    /// Steps from `from` to every open square it can reach.
+    fn distances(&self, from: Point) -> HashMap<Point, usize> {
+        let mut dist = HashMap::from([(from, 0)]);
+        let mut q = VecDeque::from([from]);
+        while let Some(p) = q.pop_front() {
+            let d = dist[&p];
+            for n in self.around(p) {
+                if self.is_open(n) && !dist.contains_key(&n) {
+                    dist.insert(n, d + 1);
+                    q.push_back(n);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Where unit `i` moves this turn: one step towards the nearest square
+    /// in range of an enemy, ties going to the square first in reading
+    /// order, along the shortest path whose first step comes first in
+    /// reading order. `None` when already in range or nothing is reachable.
+    fn choose_step(&self, i: usize) -> Option<Point> {
+        let unit = &self.units[i];
+        let in_range: Vec<Point> = self
+            .units
+            .iter()
+            .filter(|u| u.alive() && u.faction != unit.faction)
+            .flat_map(|u| self.around(u.pos))
+            .filter(|&p| p == unit.pos || self.is_open(p))
+            .collect();
+        if in_range.contains(&unit.pos) { return None }
+
+        let dist = self.distances(unit.pos);
+        let (_, target) = in_range.into_iter().filter_map(|p| Some((*dist.get(&p)?, p))).min()?;
+        let back = self.distances(target);
+        self.around(unit.pos).into_iter().filter_map(|n| Some((*back.get(&n)?, n))).min().map(|(_, n)| n)
+    }
+
+    /// The adjacent enemy unit `i` attacks: fewest HP, then reading order.
+    fn target(&self, i: usize) -> Option<usize> {
+        let unit = &self.units[i];
+        let adjacent = self.around(unit.pos);
+        (0..self.units.len())
+            .filter(|&j| {
+                let other = &self.units[j];
+                other.alive() && other.faction != unit.faction && adjacent.contains(&other.pos)
+            })
+            .min_by_key(|&j| (self.units[j].hp, self.units[j].pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######";
+
+    fn battle(map: &str) -> Battle {
+        Battle::new(map, &Rules::default()).unwrap()
+    }
+
+    fn unit_at(battle: &Battle, x: i32, y: i32) -> usize {
+        battle.units.iter().position(|u| u.pos == Point::new(x, y)).unwrap()
+    }
+
+    #[test]
+    fn test_target_square() {
+        // In range and reachable: (3, 1), (5, 2), (1, 3) and (3, 3). The
+        // nearest are (3, 1), (2, 2) and (1, 3); (3, 1) comes first.
+        let battle = battle("#######\n#E..G.#\n#...#.#\n#.G.#G#\n#######");
+        assert_eq!(battle.choose_step(unit_at(&battle, 1, 1)), Some(Point::new(2, 1)));
+    }
+
+    #[test]
+    fn test_first_step() {
+        // Right and down both lead to (4, 2) in three steps; right is first.
+        let battle = battle("#######\n#.E...#\n#.....#\n#...G.#\n#######");
+        assert_eq!(battle.choose_step(unit_at(&battle, 2, 1)), Some(Point::new(3, 1)));
+    }
+
+    #[test]
+    fn test_attack_target() {
+        // The goblins right of and below the elf tie on 2 HP; right is first.
+        let mut battle = battle("G....\n..G..\n..EG.\n..G..\n...G.");
+        for (x, y, hp) in [(0, 0, 9), (2, 1, 4), (3, 2, 2), (2, 3, 2), (3, 4, 1)] {
+            let i = unit_at(&battle, x, y);
+            battle.units[i].hp = hp;
+        }
+        let elf = unit_at(&battle, 2, 2);
+        assert_eq!(battle.target(elf), Some(unit_at(&battle, 3, 2)));
+    }
+
+    #[test]
+    fn test_movement_rounds() {
+        let mut battle = battle("#########\n#G..G..G#\n#.......#\n#.......#\n#G..E..G#\n#.......#\n#.......#\n#G..G..G#\n#########");
+        let expected = [
+            "#########\n#.G...G.#\n#...G...#\n#...E..G#\n#.G.....#\n#.......#\n#G..G..G#\n#.......#\n#########",
+            "#########\n#..G.G..#\n#...G...#\n#.G.E.G.#\n#.......#\n#G..G..G#\n#.......#\n#.......#\n#########",
+            "#########\n#.......#\n#..GGG..#\n#..GEG..#\n#G..G...#\n#......G#\n#.......#\n#.......#\n#########",
+        ];
+        for (round, grid) in expected.into_iter().enumerate() {
+            battle.round();
+            assert_eq!(battle.to_string().trim_end(), grid, "after round {}", round + 1);
+        }
+    }
+
+    #[test]
+    fn test_rounds_and_hp() {
+        // The first example, checked at the rounds the puzzle shows.
+        let mut battle = battle(EXAMPLE);
+        let hp = |battle: &Battle| battle.units.iter().sorted_by_key(|u| u.pos).map(|u| (u.faction, u.hp)).collect::<Vec<_>>();
+        battle.round();
+        assert_eq!(hp(&battle), [('G', 200), ('E', 197), ('G', 197), ('G', 200), ('G', 197), ('E', 197)]);
+        while battle.rounds() < 23 {
+            battle.round();
+        }
+        assert_eq!(hp(&battle), [('G', 200), ('G', 200), ('G', 131), ('G', 131), ('E', 131)]);
+        let outcome = battle.fight();
+        assert_eq!(outcome.rounds, 47);
+        assert_eq!(outcome.winner, Some('G'));
+        assert_eq!(outcome.deaths, BTreeMap::from([('E', 2)]));
+        assert_eq!(outcome.score(), 27730);
+    }
+
+    #[test]
+    fn test_stops_at_first_protected_death() {
+        // With 14 attack the elves of the first example lose one.
+        let outcome = battle(EXAMPLE).with_attack('E', 14).protecting('E').fight();
+        assert_eq!((outcome.winner, outcome.deaths[&'E']), (None, 1));
+        let full = battle(EXAMPLE).with_attack('E', 14).fight();
+        assert!(outcome.rounds < full.rounds);
+        assert_eq!(battle(EXAMPLE).min_attack('E').unwrap().unwrap().0, 15);
+    }
+
+    #[test]
+    fn test_min_attack_for_absent_faction() {
+        let err = battle(EXAMPLE).min_attack('O').unwrap_err();
+        assert_eq!(err.to_string(), "there are no `O` units on the map");
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = Rules::parse("diagonal = true\n[G]\nhp = 250\n[O]\nattack = 5").unwrap();
+        assert!(rules.diagonal);
+        assert_eq!(rules.faction('G'), Some(&Faction { symbol: 'G', hp: 250, attack: 3 }));
+        assert_eq!(rules.faction('E'), Some(&Faction::new('E')));
+        assert_eq!(rules.faction('O'), Some(&Faction { symbol: 'O', hp: 200, attack: 5 }));
+        assert_eq!(Rules::parse("").unwrap(), Rules::default());
+        assert!(Rules::parse("[GE]\nhp = 1").is_err());
+        assert!(Rules::parse("[#]\nhp = 1").is_err());
+        assert!(Rules::parse("[G]\nhp = 0").is_err());
+        assert!(Rules::parse("[G]\nspeed = 2").is_err());
+        assert!(Battle::new("#GO#", &Rules::default()).is_err());
+    }
+
+    #[test]
+    fn test_goblin_hp() {
+        let rules = Rules::parse("[G]\nhp = 250").unwrap();
+        let outcome = Battle::new(EXAMPLE, &rules).unwrap().fight();
+        assert_eq!(outcome.winner, Some('G'));
+        assert!(outcome.remaining_hp > battle(EXAMPLE).fight().remaining_hp);
+    }
+
+    #[test]
+    fn test_three_factions() {
+        // G and E trade blows, O kills E, then G steps up and O kills it too.
+        let rules = Rules::parse("[O]\nattack = 200").unwrap();
+        let outcome = Battle::new("#####\n#GEO#\n#####", &rules).unwrap().fight();
+        assert_eq!(outcome.winner, Some('O'));
+        assert_eq!((outcome.rounds, outcome.remaining_hp), (2, 197));
+        assert_eq!(outcome.deaths, BTreeMap::from([('E', 1), ('G', 1)]));
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let map = "#####\n#E..#\n#...#\n#..G#\n#####";
+        let straight = battle(map);
+        assert_eq!(straight.choose_step(0), Some(Point::new(2, 1)));
+        let diagonal = Battle::new(map, &Rules { diagonal: true, ..Rules::default() }).unwrap();
+        assert_eq!(diagonal.choose_step(0), Some(Point::new(2, 2)));
+        assert_eq!(diagonal.clone().fight().rounds, straight.fight().rounds - 1);
+    }
+
+    #[test]
+    fn test_replay_every_round() {
+        let mut battle = battle(EXAMPLE);
+        let mut log = String::new();
+        let mut maps = vec![battle.to_string()];
+        while !battle.over() {
+            battle.round_with(|e| log += &format!("{}\n", e));
+            maps.push(battle.to_string());
+        }
+        assert!(log.ends_with("round 47 complete\nbattle over after 47 full rounds, G wins\n"));
+        assert_eq!(log.lines().filter(|l| l.ends_with("dies")).collect::<Vec<_>>(), ["E at 4,2 dies", "E at 5,4 dies"]);
+        for (round, map) in maps.iter().enumerate() {
+            assert_eq!(replay(EXAMPLE, &log, round as i32).unwrap().to_string(), *map, "after round {}", round);
+        }
+        assert_eq!(replay(EXAMPLE, &log, 1000).unwrap().to_string(), *maps.last().unwrap());
+    }
+
+    #[test]
+    fn test_event_text() {
+        let events = [
+            Event::Moved { faction: 'G', from: Point::new(2, 1), to: Point::new(3, 1) },
+            Event::Attacked { faction: 'E', at: Point::new(4, 2), enemy: 'G', target: Point::new(5, 2), damage: 3, hp_left: 197 },
+            Event::Killed { faction: 'G', at: Point::new(5, 2) },
+            Event::RoundComplete { round: 12 },
+            Event::BattleEnded { rounds: 47, winner: Some('G') },
+            Event::BattleEnded { rounds: 3, winner: None },
+        ];
+        for event in events {
+            assert_eq!(Event::parse(&event.to_string()).unwrap(), event);
+        }
+        assert_eq!(Event::parse("G at 5,2 dies").unwrap(), Event::Killed { faction: 'G', at: Point::new(5, 2) });
+        assert!(Event::parse("G teleports").is_err());
+        assert!(Event::parse("GE at 5,2 dies").is_err());
+    }
+
+    #[test]
+    fn test_replay_rejects_inconsistent_logs() {
+        let map = "#####\n#G.E#\n#####";
+        assert!(replay(map, "G moves 1,1 -> 2,1\nround 1 complete", 1).is_ok());
+        assert!(replay(map, "E moves 1,1 -> 2,1", 1).is_err());
+        assert!(replay(map, "G moves 1,1 -> 3,1", 1).is_err());
+        assert!(replay(map, "G at 1,1 hits E at 2,1 for 3 damage, 197 HP left", 1).is_err());
+        assert!(replay(map, "round 2 complete", 2).is_err());
+    }
+
+    #[test]
+    fn test_stalemate() {
+        let outcome = battle("#######\n#G.#.E#\n#######").fight();
+        assert_eq!(outcome.winner, None);
+        assert!(outcome.deaths.is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A sequence that repeats: the state after `start` steps comes back every
+/// `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn reduce(&self, n: u64) -> usize {
+        let (start, period) = (self.start as u64, self.period as u64);
+        match n.checked_sub(start) {
+            Some(into) => (start + into % period) as usize,
+            None => n as usize,
+        }
+    }
+}
+
+/// Every state up to the first repeat, or up to the step limit.
+#[derive(Debug, Clone)]
+pub struct Run<S> {
+    /// `states[i]` is the state after `i` steps; `states[0]` is the initial one.
+    pub states: Vec<S>,
+    pub cycle: Option<Cycle>,
+}
+
+impl<S> Run<S> {
+    /// The state after `n` steps, if the run got that far or found a cycle.
+    pub fn state_at(&self, n: u64) -> Option<&S> {
+        match self.cycle {
+            Some(cycle) => self.states.get(cycle.reduce(n)),
+            None => self.states.get(usize::try_from(n).ok()?),
+        }
+    }
+}
+
+/// Applies `step` until a state repeats or `limit` steps have run,
+/// remembering every state seen in a hash map.
+pub fn find<S: Clone + Hash + Eq>(initial: S, limit: u64, mut step: impl FnMut(&S) -> S) -> Run<S> {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+    while (states.len() as u64) <= limit {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle { start, period: states.len() - start };
+            return Run { states, cycle: Some(cycle) };
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+    Run { states, cycle: None }
+}
+
+/// The state after `n` steps, skipping ahead as soon as the states cycle.
+pub fn state_at<S: Clone + Hash + Eq>(initial: S, n: u64, step: impl FnMut(&S) -> S) -> S {
+    find(initial, n, step).state_at(n).unwrap().clone()
+}
+
+/// Drift mode, for patterns that repeat while moving: only `key` of the
+/// state has to repeat, and `score` is assumed to grow by the same amount
+/// every period from then on, as a sum of positions does when the pattern
+/// only shifts. Returns the score after `n` steps.
+pub fn score_with_drift<S, K: Hash + Eq>(
+    initial: S,
+    n: u64,
+    mut step: impl FnMut(&S) -> S,
+    key: impl Fn(&S) -> K,
+    score: impl Fn(&S) -> i64,
+) -> i64 {
+    let mut seen = HashMap::new();
+    let mut scores = Vec::new();
+    let mut state = initial;
+    for i in 0.. {
+        scores.push(score(&state));
+        if i == n {
+            break;
+        }
+        if let Some(&start) = seen.get(&key(&state)) {
+            let cycle = Cycle { start, period: i as usize - start };
+            let periods = (n - start as u64) / cycle.period as u64;
+            let gain = scores[i as usize] - scores[start];
+            return scores[cycle.reduce(n)] + periods as i64 * gain;
+        }
+        seen.insert(key(&state), i as usize);
+        state = step(&state);
+    }
+    scores[n as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 leads into 3, 6, 12, 11, 9, 5, 10, 7, 1, 2, 4, 8 and back to 3.
+    fn doubling(&x: &u32) -> u32 {
+        if x == 0 { 3 } else { x * 2 % 13 }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let run = find(0, 1000, doubling);
+        assert_eq!(run.cycle, Some(Cycle { start: 1, period: 12 }));
+        assert_eq!(run.states[..4], [0, 3, 6, 12]);
+        let mut x = 0;
+        for n in 0..100 {
+            assert_eq!(run.state_at(n), Some(&x), "after {} steps", n);
+            x = doubling(&x);
+        }
+        assert_eq!(state_at(0, 1_000_000_000_000, doubling), 11);
+    }
+
+    #[test]
+    fn test_limit() {
+        let run = find(0u64, 10, |&x| x + 1);
+        assert_eq!(run.cycle, None);
+        assert_eq!(run.states.len(), 11);
+        assert_eq!(run.state_at(10), Some(&10));
+        assert_eq!(run.state_at(11), None);
+    }
+
+    #[test]
+    fn test_drift() {
+        // Two blocks that wander for a while, then march right together.
+        let step = |&(a, b): &(i64, i64)| if a < 5 { (a + 2, b) } else { (a + 1, b + 1) };
+        let score = |&(a, b): &(i64, i64)| a + b;
+        let simulated = |n: u64| (0..n).fold((0, 3), |s, _| step(&s));
+        for n in 0..40 {
+            assert_eq!(score_with_drift((0, 3), n, step, |&(a, b)| b - a, score), score(&simulated(n)), "after {} steps", n);
+        }
+        assert_eq!(score_with_drift((0, 3), 50_000_000_000, step, |&(a, b)| b - a, score), 100_000_000_003);
+    }
+}
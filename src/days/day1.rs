@@ -0,0 +1,53 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+use std::collections::HashSet;
+
+pub struct Day1(Vec<i32>);
+
+impl Solution for Day1 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day1(parse::lines(input, |line| parse::number(line, line))?))
+    }
+    fn part1(&self) -> Answer {
+        let mut acc = 0;
+        for x in self.0.iter() {
+            acc += x;
+        }
+        acc.into()
+    }
+    fn part2(&self) -> Answer {
+        let mut seen: HashSet<i32> = HashSet::new();
+        let mut acc = 0;
+        seen.insert(acc);
+        for x in self.0.iter().cycle() {
+            acc += x;
+            if !seen.insert(acc) {
+                break;
+            }
+        }
+        acc.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("+1\n-2\n+3\n+1" => "3")]
+    #[test_case("+1\n+1\n+1" => "3")]
+    #[test_case("+1\n+1\n-2" => "0")]
+    #[test_case("-1\n-2\n-3" => "-6")]
+    fn test_part1(input: &str) -> String {
+        Day1::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case("+1\n-2\n+3\n+1" => "2")]
+    #[test_case("+1\n-1" => "0")]
+    #[test_case("+3\n+3\n+4\n-2\n-4" => "10")]
+    #[test_case("-6\n+3\n+8\n+5\n-6" => "5")]
+    #[test_case("+7\n+7\n-2\n-7\n-4" => "14")]
+    fn test_part2(input: &str) -> String {
+        Day1::parse(input).unwrap().part2().to_string()
+    }
+}
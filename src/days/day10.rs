@@ -0,0 +1,171 @@
+// This is not my solution
+
+use crate::{geometry::{Bounds, Point}, parse, solution::{Answer, Solution}};
+use color_eyre::eyre::{ensure, Result};
+use std::collections::HashSet;
+
+/// Parses `<x, y>` where `s` is a subslice of `line`.
+fn parse_point(line: &str, s: &str) -> Result<Point> {
+    let s = parse::strip_prefix(line, s.trim(), "<")?;
+    let s = parse::strip_suffix(line, s, ">")?;
+    let (x, y) = parse::split_once(line, s, ",")?;
+
+    Ok(Point {
+        x: parse::number(line, x)?,
+        y: parse::number(line, y)?,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Star {
+    id: usize,
+    position: Point,
+    velocity: Point,
+}
+
+fn parse_stars(input: &str) -> Result<HashSet<Star>> {
+    let stars = parse::lines(input, |line| {
+        let (position, velocity) = parse::split_once(line, line, " velocity=")?;
+        let position = parse::strip_prefix(line, position, "position=")?;
+        Ok((parse_point(line, position)?, parse_point(line, velocity)?))
+    })?;
+    ensure!(!stars.is_empty(), "the input has no stars");
+    Ok(stars
+        .into_iter()
+        .enumerate()
+        .map(|(id, (position, velocity))| Star { id, position, velocity })
+        .collect())
+}
+
+fn bounds(stars: &HashSet<Star>) -> Bounds {
+    Bounds::of(stars.iter().map(|star| star.position)).expect("there is at least one star")
+}
+
+/// Moves the stars until they are packed tightest vertically, returning the
+/// rendered message and how many seconds that took. The height is smallest
+/// when two stars pass each other vertically, and any two that do so pass
+/// within the starting height in seconds, so the search stops there.
+fn align(set: HashSet<Star>) -> (String, u32) {
+    let mut stars = set;
+    let mut new_stars = stars.clone();
+    let mut size = bounds(&stars).height();
+    let limit = size;
+    let mut new_size;
+    let mut counter = 0;
+    while counter < limit {
+        new_stars.clear();
+        for star in stars.iter() {
+            new_stars.insert(Star {
+                position: star.position + star.velocity,
+                ..*star
+            });
+        }
+        new_size = bounds(&new_stars).height();
+
+        if new_size > size {
+            break;
+        }
+        counter += 1;
+        // double buffer
+        std::mem::swap(&mut stars, &mut new_stars);
+        std::mem::swap(&mut size, &mut new_size);
+    }
+
+    let bounds = bounds(&stars);
+    let stars: HashSet<Point> = stars
+        .iter()
+        .map(|star| star.position)
+        .collect();
+    let mut result = String::new();
+    for y in bounds.min.y..=bounds.max.y {
+        for x in bounds.min.x..=bounds.max.x {
+            let dot = if stars.contains(&Point { x, y }) { '#' } else { '.' };
+            result.push(dot);
+        }
+        result.push('\n');
+    }
+
+    (result, counter)
+}
+
+pub struct Day10(HashSet<Star>);
+
+impl Solution for Day10 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day10(parse_stars(input)?))
+    }
+    fn part1(&self) -> Answer {
+        align(self.0.clone()).0.into()
+    }
+    fn part2(&self) -> Answer {
+        align(self.0.clone()).1.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+    const HI: &str = "#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###
+";
+
+    #[test]
+    fn test_align() {
+        let (message, seconds) = align(parse_stars(EXAMPLE).unwrap());
+        assert_eq!(message, HI);
+        assert_eq!(seconds, 3);
+    }
+
+    #[test]
+    fn test_height_never_shrinks() {
+        // Drifting sideways only, or standing still: stop rather than wait forever.
+        let (message, seconds) = align(parse_stars("position=<0, 0> velocity=<1, 0>\nposition=<0, 2> velocity=<1, 0>").unwrap());
+        assert_eq!((message.as_str(), seconds), ("#\n.\n#\n", 3));
+        let (message, _) = align(parse_stars("position=<5, 5> velocity=<0, 0>").unwrap());
+        assert_eq!(message, "#\n");
+    }
+
+    #[test]
+    fn test_rejects_no_stars() {
+        assert!(Day10::parse("").is_err());
+    }
+}
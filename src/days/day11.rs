@@ -0,0 +1,112 @@
+use crate::{grid::Grid, parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+
+/// Power level of every fuel cell, `(0, 0)` being the cell at 1,1.
+#[derive(Debug, Clone)]
+struct FuelCells(Grid<i32>);
+
+impl FuelCells {
+    fn new(ser_num: i32) -> Self {
+        Self(Grid::from_fn(300, 300, |(x, y)| power_level(ser_num, x, y)))
+    }
+    fn find_square_part1(&self) -> (usize, usize, i32) {
+        let grid = &self.0;
+        let (x, y, sum) = (0..=grid.height() - 3).flat_map(|y| {
+            (0..=grid.width() - 3).map(move |x| {
+                let sum = (y..y + 3).flat_map(|small_y| {
+                    (x..x + 3).map(move |small_x| {
+                        grid[(small_x, small_y)]
+                    })
+                }).sum::<i32>();
+                (x, y, sum)
+            })
+        }).max_by_key(|&(_, _, sum)| sum).unwrap();
+        (x + 1, y + 1, sum)
+    }
+    fn find_square_part2(&self) -> (usize, usize, usize, i32) {
+        let rows = self.0.height();
+        let cols = self.0.width();
+        let mut prefix_sum = Grid::new(cols + 1, rows + 1, 0);
+
+        for y in 1..=rows {
+            for x in 1..=cols {
+                prefix_sum[(x, y)] = self.0[(x - 1, y - 1)]
+                    + prefix_sum[(x, y - 1)]
+                    + prefix_sum[(x - 1, y)]
+                    - prefix_sum[(x - 1, y - 1)];
+            }
+        }
+
+        let mut max_sum = i32::MIN;
+        let mut result = (0, 0);
+        let mut square = 0;
+
+        for area in 1..=rows.min(cols) {
+            for y in area..=rows {
+                for x in area..=cols {
+                    let sum = prefix_sum[(x, y)]
+                        - prefix_sum[(x, y - area)]
+                        - prefix_sum[(x - area, y)]
+                        + prefix_sum[(x - area, y - area)];
+
+                    if sum > max_sum {
+                        max_sum = sum;
+                        result = (x - area, y - area);
+                        square = area;
+                    }
+                }
+            }
+        }
+
+        (result.0 + 1, result.1 + 1, square, max_sum)
+    }
+}
+
+/// Power level of the cell at 0-based `(x, y)`.
+fn power_level(ser_num: i32, x: usize, y: usize) -> i32 {
+    let rack_id = x as i32 + 11;
+    let pow_lev = ((y as i32 + 1) * rack_id + ser_num) * rack_id;
+    pow_lev % 1000 / 100 - 5
+}
+
+pub struct Day11(FuelCells);
+
+impl Solution for Day11 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day11(FuelCells::new(parse::number(input, input)?)))
+    }
+    fn part1(&self) -> Answer {
+        let (x, y, _sum) = self.0.find_square_part1();
+        (x, y).into()
+    }
+    fn part2(&self) -> Answer {
+        let (x, y, sq, _sum) = self.0.find_square_part2();
+        (x, y, sq).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(8, 3, 5 => 4)]
+    #[test_case(57, 122, 79 => -5)]
+    #[test_case(39, 217, 196 => 0)]
+    #[test_case(71, 101, 153 => 4)]
+    fn test_power_level(serial: i32, x: usize, y: usize) -> i32 {
+        power_level(serial, x - 1, y - 1)
+    }
+
+    #[test_case("18" => "33,45")]
+    #[test_case("42" => "21,61")]
+    fn test_part1(input: &str) -> String {
+        Day11::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case("18" => "90,269,16")]
+    #[test_case("42" => "232,251,12")]
+    fn test_part2(input: &str) -> String {
+        Day11::parse(input).unwrap().part2().to_string()
+    }
+}
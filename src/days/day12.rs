@@ -0,0 +1,153 @@
+use crate::{cycle, parse, solution::{Answer, Solution}};
+use color_eyre::eyre::{eyre, Result};
+use std::collections::HashMap;
+use itertools::Itertools;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct Pots {
+    row: Vec<char>,
+    state: i64,
+    map: HashMap<Vec<char>, char>,
+}
+
+impl fmt::Display for Pots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in &self.row {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl Pots {
+    fn new(row: Vec<char>) -> Self {
+        let offset = row.iter().position(|&x| x == '#').unwrap() as i64;
+        Pots {
+            row,
+            state: offset,
+            map: HashMap::new(),
+        }
+    }
+    fn next_gen(&mut self) {
+        for _ in 0..3 {
+            self.row.insert(0, '.');
+            self.row.push('.');
+        }
+        self.state += 3;
+        let row_len = self.row.len();
+        let mut new = vec!['.'; row_len];
+    
+        self.row.windows(5)
+            .enumerate()
+            .for_each(|(idx, win)| {
+                if let Some(&c) = self.map.get(win) {
+                    new[idx + 2] = c;
+                }
+            });
+    
+        self.row = new;
+    }
+    fn stepped(&self) -> Self {
+        let mut next = self.clone();
+        next.next_gen();
+        next
+    }
+    /// The plants from the first to the last, wherever the row has moved.
+    fn shape(&self) -> Vec<char> {
+        let first = self.row.iter().position(|&c| c == '#').unwrap_or(0);
+        let last = self.row.iter().rposition(|&c| c == '#').map_or(0, |i| i + 1);
+        self.row[first..last.max(first)].to_vec()
+    }
+    fn find_sum(&self) -> i64 {
+        self.row.iter().positions(|&x| x == '#').map(|x| x as i64 - self.state).sum::<i64>()
+    }
+}
+fn pot_row(line: &str, span: &str) -> Result<Vec<char>> {
+    match span.find(|c| c != '#' && c != '.') {
+        Some(idx) => Err(parse::error_at(line, &span[idx..], "pots must be `#` or `.`")),
+        None => Ok(span.chars().collect()),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Pots> {
+    let (state, map) = match parse::blocks(input)[..] {
+        [state, map] => (state, map),
+        _ => return Err(eyre!("expected a blank line between the initial state and the rules")),
+    };
+    let line = state.trim();
+    let state = parse::strip_prefix(line, line, "initial state: ")?;
+    let row = pot_row(line, state)?;
+    if !row.contains(&'#') {
+        return Err(parse::error_at(line, state, "the initial state has no plants"));
+    }
+    let mut pots = Pots::new(row);
+    let rules = parse::lines_at(input, map, |line| {
+        let (key, value) = parse::split_once(line, line.trim(), " => ")?;
+        if key.len() != 5 {
+            return Err(parse::error_at(line, key, "a rule needs exactly five pots"));
+        }
+        if value.len() != 1 {
+            return Err(parse::error_at(line, value, "a rule produces a single pot"));
+        }
+        Ok((pot_row(line, key)?, pot_row(line, value)?[0]))
+    })?;
+    pots.map.extend(rules);
+    Ok(pots)
+}
+pub struct Day12(Pots);
+
+impl Solution for Day12 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day12(parse_input(input)?))
+    }
+    fn part1(&self) -> Answer {
+        let mut pots = self.0.clone();
+        for _ in 0..20 {
+            pots.next_gen();
+        }
+        pots.find_sum().into()
+    }
+    fn part2(&self) -> Answer {
+        cycle::score_with_drift(self.0.clone(), 50_000_000_000, Pots::stepped, Pots::shape, Pots::find_sum).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #";
+
+    #[test_case(EXAMPLE => "325")]
+    fn test_part1(input: &str) -> String {
+        Day12::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test]
+    fn test_drift_matches_simulation() {
+        let start = Day12::parse(EXAMPLE).unwrap().0;
+        let mut pots = start.clone();
+        for gen in 1..=150 {
+            pots.next_gen();
+            let drift = cycle::score_with_drift(start.clone(), gen, Pots::stepped, Pots::shape, Pots::find_sum);
+            assert_eq!(drift, pots.find_sum(), "generation {}", gen);
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{frames::{Recorder, Snapshot}, geometry::{Direction, Point}, grid::Grid, parse, solution::{Answer, Solution}};
+use color_eyre::eyre::{bail, ensure, Result};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    fmt,
+};
+
+/// What a cart does at its next intersection: left, straight, then right,
+/// and round again.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+enum NextTurn {
+    Left,
+    Straight,
+    Right,
+}
+
+impl NextTurn {
+    fn apply(self, dir: Direction) -> Direction {
+        match self {
+            NextTurn::Left => dir.turn_left(),
+            NextTurn::Straight => dir,
+            NextTurn::Right => dir.turn_right(),
+        }
+    }
+    fn next(self) -> Self {
+        match self {
+            NextTurn::Left => NextTurn::Straight,
+            NextTurn::Straight => NextTurn::Right,
+            NextTurn::Right => NextTurn::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct Cart {
+    pos: Point,
+    dir: Direction,
+    next_turn: NextTurn,
+}
+
+impl Ord for Cart {
+    // Reverse order because we'll take carts from the end of the vector after sorting
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.pos.cmp(&self.pos)
+    }
+}
+
+impl PartialOrd for Cart {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Cart {
+    fn new(pos: Point, dir: Direction) -> Self {
+        Self { pos, dir, next_turn: NextTurn::Left }
+    }
+    fn turn(&mut self) {
+        self.dir = self.next_turn.apply(self.dir);
+        self.next_turn = self.next_turn.next();
+    }
+}
+
+/// The direction a cart heading `dir` leaves `piece` in, or `None` when the
+/// piece does not take carts from that side. Carts go straight over
+/// intersections unless they turn there.
+fn follow(piece: char, dir: Direction) -> Option<Direction> {
+    match piece {
+        '-' if !dir.is_vertical() => Some(dir),
+        '|' if dir.is_vertical() => Some(dir),
+        '+' => Some(dir),
+        '\\' if dir.is_vertical() => Some(dir.turn_left()),
+        '\\' => Some(dir.turn_right()),
+        '/' if dir.is_vertical() => Some(dir.turn_right()),
+        '/' => Some(dir.turn_left()),
+        _ => None,
+    }
+}
+
+/// Ticks after which carts that have not crashed are taken to be on tracks
+/// that never meet; real inputs are done in a few tens of thousands.
+const MAX_TICKS: usize = 1_000_000;
+
+#[derive(Debug, Clone)]
+struct Tracks {
+    trks: Grid<char>,
+    carts: Vec<Cart>,
+    /// Where carts have collided so far.
+    crashes: Vec<Point>,
+}
+
+impl fmt::Display for Tracks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.snapshot())
+    }
+}
+
+impl Tracks {
+    fn new(trks: Grid<char>, carts: Vec<Cart>) -> Self {
+        Self { trks, carts, crashes: Vec::new() }
+    }
+    fn adj_dir(&self, cart: &mut Cart) {
+        cart.pos = cart.pos.step(cart.dir);
+        match self.trks[cart.pos] {
+            '+' => cart.turn(),
+            // `validate` made sure every piece a cart can reach takes it.
+            piece => cart.dir = follow(piece, cart.dir).unwrap(),
+        }
+    }
+    
+    fn check_collisions(&self, cart: &Cart, moved_carts: &[Cart]) -> bool {
+        moved_carts.iter().any(|c| c.pos == cart.pos) || self.carts.iter().any(|c| c.pos == cart.pos)
+    }
+    fn find_first_cart(&mut self) -> Option<Point> {
+        self.carts.sort_unstable();
+        let mut moved_carts: Vec<Cart> = Vec::new();
+    
+        while let Some(mut cart) = self.carts.pop() {
+            self.adj_dir(&mut cart);
+    
+            if self.check_collisions(&cart, &moved_carts) {
+                self.crashes.push(cart.pos);
+                return Some(cart.pos);
+            }
+            else { moved_carts.push(cart) }
+        }
+        self.carts = moved_carts;
+        None
+    }
+
+    fn find_last_cart(&mut self) -> Option<Point> {
+        self.carts.sort_unstable();
+        let mut moved_carts: Vec<Cart> = Vec::new();
+
+        while let Some(mut cart) = self.carts.pop() {
+            self.adj_dir(&mut cart);
+    
+            if self.check_collisions(&cart, &moved_carts) {
+                self.crashes.push(cart.pos);
+                moved_carts.retain(|c| c.pos != cart.pos);
+                self.carts.retain(|c| c.pos != cart.pos);
+            } 
+            else { moved_carts.push(cart) }
+        }
+        self.carts = moved_carts;
+        if self.carts.len() == 1 { return Some(self.carts[0].pos) }
+        None
+    }
+}
+
+/// The track drawn with the carts on it and an `X` at every crash.
+impl Snapshot for Tracks {
+    fn snapshot(&self) -> Grid<char> {
+        let mut frame = self.trks.clone();
+        for cart in &self.carts {
+            frame[cart.pos] = cart_glyph(cart.dir);
+        }
+        for &crash in &self.crashes {
+            frame[crash] = 'X';
+        }
+        frame
+    }
+}
+
+fn cart_glyph(dir: Direction) -> char {
+    match dir {
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        Direction::Up => '^',
+        Direction::Left => '<',
+    }
+}
+
+fn parse_cart(c: char) -> Option<Direction> {
+    match c {
+        '>' => Some(Direction::Right),
+        'v' => Some(Direction::Down),
+        '^' => Some(Direction::Up),
+        '<' => Some(Direction::Left),
+        _ => None,
+    }
+}
+
+/// Follows every way the carts could go, taking each choice at every
+/// intersection, so no cart can run off the track or into a dead end once
+/// the simulation starts.
+fn validate(trks: &Grid<char>, carts: &[Cart]) -> Result<()> {
+    let mut seen = HashSet::new();
+    let mut todo: Vec<(Point, Direction)> = carts.iter().map(|c| (c.pos, c.dir)).collect();
+    while let Some((pos, dir)) = todo.pop() {
+        if !seen.insert((pos, dir)) { continue }
+        let next = pos.step(dir);
+        let piece = match trks.at(next) {
+            Some(&piece) if piece != ' ' => piece,
+            _ => bail!("a cart heading {:?} from {} runs off the track", dir, pos),
+        };
+        let Some(out) = follow(piece, dir) else {
+            bail!("dead end at {}: a cart heading {:?} runs into `{}`", next, dir, piece)
+        };
+        match piece {
+            '+' => todo.extend([dir.turn_left(), dir, dir.turn_right()].map(|d| (next, d))),
+            _ => todo.push((next, out)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_tracks(input: &str) -> Result<Tracks> {
+    let rows = parse::char_grid(input, |c| matches!(c, ' '|'-'|'|'|'/'|'\\'|'+'|'>'|'v'|'^'|'<'), "track piece")?;
+    // Editors tend to strip the trailing spaces, so short rows are padded back out.
+    let mut map = Grid::from_rows_padded(rows, ' ');
+    let carts: Vec<Cart> = map.iter()
+        .filter_map(|(p, &c)| Some(Cart::new(p.into(), parse_cart(c)?)))
+        .collect();
+    ensure!(!carts.is_empty(), "the map has no carts");
+    // Carts always start on straight track, running along it.
+    for cart in &carts {
+        map[cart.pos] = if cart.dir.is_vertical() { '|' } else { '-' };
+    }
+    validate(&map, &carts)?;
+    Ok(Tracks::new(map, carts))
+}
+
+/// Records the carts until at most one is left, one frame per tick.
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<usize> {
+    let mut tracks = parse_tracks(input)?;
+    recorder.capture(&tracks);
+    for _ in 0..MAX_TICKS {
+        if tracks.carts.len() < 2 {
+            return recorder.finish(&tracks);
+        }
+        tracks.find_last_cart();
+        recorder.capture(&tracks);
+    }
+    bail!("{} carts are still running after {} ticks; their tracks may never meet", tracks.carts.len(), MAX_TICKS)
+}
+
+pub struct Day13(Tracks);
+
+impl Solution for Day13 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day13(parse_tracks(input)?))
+    }
+    fn part1(&self) -> Answer {
+        let mut tracks = self.0.clone();
+        if tracks.carts.len() < 2 { return Answer::Unsolved }
+
+        for _ in 0..MAX_TICKS {
+            if let Some(crash) = tracks.find_first_cart() {
+                return crash.to_usize().unwrap().into();
+            }
+        }
+        Answer::Unsolved
+    }
+    fn part2(&self) -> Answer {
+        let mut tracks = self.0.clone();
+        if tracks.carts.len() < 2 { return Answer::Unsolved }
+
+        for _ in 0..MAX_TICKS {
+            if let Some(last) = tracks.find_last_cart() {
+                return last.to_usize().unwrap().into();
+            }
+            // Every cart crashed and none is left over.
+            if tracks.carts.is_empty() { break }
+        }
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    // Kept as separate lines so the trailing spaces padding each row survive.
+    const CRASH: &[&str] = &[
+        r"/->-\        ",
+        r"|   |  /----\",
+        r"| /-+--+-\  |",
+        r"| | |  | v  |",
+        r"\-+-/  \-+--/",
+        r"  \------/   ",
+    ];
+
+    const LAST_CART: &[&str] = &[
+        r"/>-<\  ",
+        r"|   |  ",
+        r"| /<+-\",
+        r"| | | v",
+        r"\>+</ |",
+        r"  |   ^",
+        r"  \<->/",
+    ];
+
+    // One cart on a loop, two that destroy each other, two on loops of their own.
+    const ONE_CART: &str = "/>\\\n\\-/";
+    const HEAD_ON: &str = "/>-<\\\n\\---/";
+    const APART: &str = "/>\\ /<\\\n\\-/ \\-/";
+
+    #[test_case(ONE_CART => "unsolved")]
+    #[test_case(HEAD_ON => "2,0")]
+    #[test_case(APART => "unsolved")]
+    fn test_part1_edge_cases(input: &str) -> String {
+        Day13::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(ONE_CART => "unsolved")]
+    #[test_case(HEAD_ON => "unsolved")]
+    fn test_part2_edge_cases(input: &str) -> String {
+        Day13::parse(input).unwrap().part2().to_string()
+    }
+
+    #[test]
+    fn test_record_stops() {
+        let mut recorder = Recorder::ascii(std::io::sink());
+        assert_eq!(record(HEAD_ON, &mut recorder).unwrap(), 2);
+        let mut recorder = Recorder::ascii(std::io::sink()).every(MAX_TICKS);
+        assert!(record(APART, &mut recorder).unwrap_err().to_string().contains("may never meet"));
+    }
+
+    #[test_case(CRASH => "7,3")]
+    fn test_part1(lines: &[&str]) -> String {
+        Day13::parse(&lines.join("\n")).unwrap().part1().to_string()
+    }
+
+    #[test_case(LAST_CART => "6,4")]
+    fn test_part2(lines: &[&str]) -> String {
+        Day13::parse(&lines.join("\n")).unwrap().part2().to_string()
+    }
+
+    #[test]
+    fn test_turns_cycle() {
+        let mut cart = Cart::new(Point::ORIGIN, Direction::Up);
+        let dirs: Vec<Direction> = (0..4).map(|_| { cart.turn(); cart.dir }).collect();
+        assert_eq!(dirs, [Direction::Left, Direction::Left, Direction::Up, Direction::Left]);
+        assert_eq!(cart.next_turn, NextTurn::Straight);
+    }
+
+    #[test]
+    fn test_cart_glyphs_become_track() {
+        let tracks = parse_tracks(&CRASH.join("\n")).unwrap();
+        assert_eq!((tracks.trks[(2, 0)], tracks.trks[(9, 3)]), ('-', '|'));
+        assert_eq!(tracks.snapshot()[(2, 0)], '>');
+        assert_eq!(tracks.to_string().lines().collect::<Vec<_>>(), CRASH);
+    }
+
+    #[test_case("/>\\\n\\-/" => None; "closed loop")]
+    #[test_case("->- " => Some("a cart heading Right from 2,0 runs off the track".to_string()); "gap")]
+    #[test_case("<-" => Some("a cart heading Left from 0,0 runs off the track".to_string()); "edge")]
+    #[test_case("/>-|\n\\--/" => Some("dead end at 3,0: a cart heading Right runs into `|`".to_string()); "dead end")]
+    #[test_case("/>+-\\\n\\-+-/\n  |  " => Some("a cart heading Down from 2,2 runs off the track".to_string()); "open branch")]
+    fn test_validation(map: &str) -> Option<String> {
+        parse_tracks(map).err().map(|e| e.to_string())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+
+#[derive(Debug)]
+struct Recipes {
+    board: Vec<usize>,
+    elfs: [usize; 2],
+}
+
+impl Recipes {
+    fn new() -> Self {
+        Self {
+            board: vec![3, 7],
+            elfs: [0, 1],
+        }
+    }
+    fn add_rps(&mut self) {
+        let new: usize = self.elfs.iter().map(|&elf| self.board[elf]).sum();
+        if new > 9 { self.board.push(new / 10) }
+        self.board.push(new % 10);
+    }
+    fn pick_rps(&mut self) {
+        for i in self.elfs.iter_mut() {*i = (*i + self.board[*i] + 1) % self.board.len()}
+    }
+    fn round(&mut self) {
+        self.add_rps();
+        self.pick_rps();
+    }
+    fn find_score(&self, rps: usize) -> String {
+        self.board[rps..rps + 10].iter().map(|d| d.to_string()).collect()
+    }
+    fn find_rps(&mut self, rps: &str) -> usize {
+        let slice: Vec<usize> = rps.split("").filter_map(|c| c.parse::<usize>().ok()).collect();
+
+        loop {
+            self.round();
+            let len = self.board.len();
+            if len > slice.len() {
+                for i in 0..=1 {
+                    if self.board[len - slice.len() - i..len - i] == slice {
+                        return len - slice.len() - i;
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct Day14(String);
+
+impl Solution for Day14 {
+    fn parse(input: &str) -> Result<Self> {
+        let line = input.trim();
+        parse::number::<usize>(line, line)?;
+        Ok(Day14(line.to_string()))
+    }
+    fn part1(&self) -> Answer {
+        let mut board = Recipes::new();
+        let rps = self.0.parse::<usize>().unwrap();
+        while board.board.len() < rps + 10 {board.round()}
+        board.find_score(rps).into()
+    }
+    fn part2(&self) -> Answer {
+        let mut board = Recipes::new();
+        board.find_rps(&self.0).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("9" => "5158916779")]
+    #[test_case("5" => "0124515891")]
+    #[test_case("18" => "9251071085")]
+    #[test_case("2018" => "5941429882")]
+    fn test_part1(input: &str) -> String {
+        Day14::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case("51589" => "9")]
+    #[test_case("01245" => "5")]
+    #[test_case("92510" => "18")]
+    #[test_case("59414" => "2018")]
+    fn test_part2(input: &str) -> String {
+        Day14::parse(input).unwrap().part2().to_string()
+    }
+}
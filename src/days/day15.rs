@@ -0,0 +1,120 @@
+use crate::{
+    combat::{Battle, Rules},
+    frames::Recorder,
+    solution::{Answer, Solution},
+};
+use color_eyre::eyre::Result;
+
+/// Records the part 1 battle, one frame per round.
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<usize> {
+    let mut battle = Battle::new(input, &Rules::default())?;
+    while !battle.over() {
+        recorder.capture(&battle);
+        battle.round();
+    }
+    recorder.finish(&battle)
+}
+
+pub struct Day15(Battle);
+
+impl Solution for Day15 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day15(Battle::new(input, &Rules::default())?))
+    }
+    fn part1(&self) -> Answer {
+        self.0.clone().fight().score().into()
+    }
+    fn part2(&self) -> Answer {
+        match self.0.min_attack('E') {
+            Ok(Some((_, outcome))) => outcome.score().into(),
+            Ok(None) => Answer::Unsolved,
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const MOVEMENT: &str = "#######
+#.G...#
+#...EG#
+#.#.#G#
+#..G#E#
+#.....#
+#######";
+
+    const ELVES_WIN: &str = "#######
+#G..#E#
+#E#E.E#
+#G.##.#
+#...#E#
+#...E.#
+#######";
+
+    const SECOND: &str = "#######
+#E..EG#
+#.#G.E#
+#E.##E#
+#G..#.#
+#..E#.#
+#######";
+
+    const THIRD: &str = "#######
+#E.G#.#
+#.#G..#
+#G.#.G#
+#G..#.#
+#...E.#
+#######";
+
+    const FOURTH: &str = "#######
+#.E...#
+#.#..G#
+#.###.#
+#E#G#G#
+#...#G#
+#######";
+
+    const LARGE: &str = "#########
+#G......#
+#.E.#...#
+#..##..G#
+#...##..#
+#...#...#
+#.G...G.#
+#.....G.#
+#########";
+
+    #[test_case(MOVEMENT => "27730")]
+    #[test_case(ELVES_WIN => "36334")]
+    #[test_case(SECOND => "39514")]
+    #[test_case(THIRD => "27755")]
+    #[test_case(FOURTH => "28944")]
+    #[test_case(LARGE => "18740")]
+    fn test_part1(input: &str) -> String {
+        Day15::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(MOVEMENT => (15, 4988))]
+    #[test_case(SECOND => (4, 31284))]
+    #[test_case(THIRD => (15, 3478))]
+    #[test_case(FOURTH => (12, 6474))]
+    #[test_case(LARGE => (34, 1140))]
+    fn test_min_elf_attack(input: &str) -> (i32, i32) {
+        let (attack, outcome) = Day15::parse(input).unwrap().0.min_attack('E').unwrap().unwrap();
+        assert!(!outcome.deaths.contains_key(&'E'));
+        (attack, outcome.score())
+    }
+
+    #[test_case(MOVEMENT => "4988")]
+    #[test_case(SECOND => "31284")]
+    #[test_case(THIRD => "3478")]
+    #[test_case(FOURTH => "6474")]
+    #[test_case(LARGE => "1140")]
+    fn test_part2(input: &str) -> String {
+        Day15::parse(input).unwrap().part2().to_string()
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    elfcode::{Instruction, Machine, Opcode, Program},
+    inference::Candidates,
+    parse,
+    solution::{Answer, Solution},
+};
+use color_eyre::eyre::{eyre, Report, Result};
+use std::str::FromStr;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1},
+    combinator::{all_consuming, map_res},
+    multi::separated_list0,
+    sequence::{delimited, preceded},
+    IResult,
+};
+
+pub(crate) fn parse_usize(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse::<usize>)(input)
+}
+
+fn parse_array<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<usize>> {
+    all_consuming(preceded(
+        tag(label),
+        delimited(
+            char('['),
+            separated_list0(tag(", "), parse_usize),
+            char(']'),
+        ),
+    ))
+}
+
+fn parse_rgts(input: &str) -> IResult<&str, Vec<usize>> {
+    all_consuming(separated_list0(tag(" "), parse_usize))(input)
+}
+
+fn vec_to_array(line: &str, vec: Vec<usize>) -> Result<[usize; 4]> {
+    let len = vec.len();
+    vec.try_into().map_err(|_| parse::error_at(line, line, format!("expected 4 values, found {}", len)))
+}
+
+fn parse_instruction(line: &str) -> Result<[usize; 4]> {
+    let cmd = vec_to_array(line, parse::nom(line, parse_rgts(line.trim()), "`opcode A B C`")?)?;
+    if cmd[3] > 3 {
+        return Err(parse::error_at(line, line.split_ascii_whitespace().last().unwrap(), "output register must be 0-3"));
+    }
+    Ok(cmd)
+}
+
+/// Parses one `Before:` / instruction / `After:` sample. `block` is a
+/// subslice of `input`, which is only used for line numbers.
+fn parse_sample(input: &str, block: &str) -> Result<Cmd> {
+    let mut idx = 0;
+    let arrays = parse::lines_at(input, block, |line| {
+        idx += 1;
+        match idx {
+            1 => vec_to_array(line, parse::nom(line, parse_array("Before: ")(line.trim()), "`Before: [a, b, c, d]`")?),
+            2 => parse_instruction(line),
+            3 => vec_to_array(line, parse::nom(line, parse_array("After:  ")(line.trim()), "`After:  [a, b, c, d]`")?),
+            _ => Err(parse::error_at(line, line, "a sample has only 3 lines")),
+        }
+    })?;
+    match arrays[..] {
+        [before, cmd, after] => Ok(Cmd { before, cmd, after }),
+        _ => Err(eyre!("line {}: a sample needs 3 lines, found {}", parse::line_of(input, block), arrays.len())),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cmd {
+    before: [usize; 4],
+    cmd: [usize; 4],
+    after: [usize; 4],
+}
+
+impl FromStr for Cmd {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_sample(s, s)
+    }
+}
+
+/// Whether the sample's instruction does what `op` would do.
+fn behaves_like(sample: &Cmd, op: Opcode) -> bool {
+    let [_, a, b, c] = sample.cmd;
+    let mut regs = sample.before;
+    Instruction::new(op, a, b, c).execute(&mut regs) && regs == sample.after
+}
+
+/// Samples that behave like three or more opcodes.
+fn count_ambiguous(samples: &[Cmd]) -> usize {
+    samples
+        .iter()
+        .filter(|sample| Opcode::ALL.into_iter().filter(|&op| behaves_like(sample, op)).count() >= 3)
+        .count()
+}
+
+/// What each opcode number could be, given every sample.
+fn candidates(samples: &[Cmd]) -> Candidates {
+    let mut candidates = Candidates::new();
+    for sample in samples {
+        candidates.observe(sample.cmd[0], Opcode::ALL.into_iter().filter(|&op| behaves_like(sample, op)));
+    }
+    candidates
+}
+
+fn parse_input(input: &str) -> Result<(Vec<[usize; 4]>, Vec<Cmd>)> {
+    let blocks = parse::blocks(input);
+    let (test_program, cmds) = match blocks.split_last() {
+        Some((last, cmds)) if !last.starts_with("Before:") => (*last, cmds),
+        _ => return Err(eyre!("expected the test program after the samples")),
+    };
+    let samples = cmds.iter().map(|c| parse_sample(input, c)).collect::<Result<_>>()?;
+    let program = parse::lines_at(input, test_program, parse_instruction)?;
+    Ok((program, samples))
+}
+
+pub struct Day16 {
+    samples: Vec<Cmd>,
+    candidates: Candidates,
+    program: Vec<[usize; 4]>,
+}
+
+impl Day16 {
+    /// Decodes the test program with the inferred opcodes and runs it.
+    /// Part 1 never needs the opcodes, so a failed inference only fails here.
+    fn run_program(&self) -> Result<usize> {
+        let ctgs = self.candidates.solve()?;
+        let instructions = self
+            .program
+            .iter()
+            .map(|&[n, a, b, c]| match ctgs.get(&n) {
+                Some(&op) => Ok(Instruction::new(op, a, b, c)),
+                None => Err(eyre!("opcode {} never appears in the samples", n)),
+            })
+            .collect::<Result<_>>()?;
+        let mut machine = Machine::new(Program { ip_reg: None, instructions }, 4)?;
+        machine.run()?;
+        Ok(machine.regs[0])
+    }
+}
+
+impl Solution for Day16 {
+    fn parse(input: &str) -> Result<Self> {
+        let (program, samples) = parse_input(input)?;
+        let candidates = candidates(&samples);
+        Ok(Day16 { samples, candidates, program })
+    }
+    fn part1(&self) -> Answer {
+        count_ambiguous(&self.samples).into()
+    }
+    fn part2(&self) -> Answer {
+        match self.run_program() {
+            Ok(r0) => r0.into(),
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = "Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]";
+
+    #[test_case(Opcode::Mulr => true)]
+    #[test_case(Opcode::Addi => true)]
+    #[test_case(Opcode::Seti => true)]
+    #[test_case(Opcode::Addr => false)]
+    #[test_case(Opcode::Eqrr => false)]
+    fn test_sample_matches(op: Opcode) -> bool {
+        behaves_like(&SAMPLE.parse().unwrap(), op)
+    }
+
+    #[test]
+    fn test_count_ambiguous() {
+        assert_eq!(count_ambiguous(&[SAMPLE.parse().unwrap()]), 1);
+    }
+
+    #[test]
+    fn test_ambiguous_samples_are_an_error() {
+        let err = candidates(&[SAMPLE.parse().unwrap()]).solve().unwrap_err();
+        assert_eq!(err.to_string(), "the samples do not pin down opcode 9: it could be addi or mulr");
+        assert_eq!(candidates(&[SAMPLE.parse().unwrap()]).get(9).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_only_part2_needs_the_opcodes() {
+        let day = Day16::parse(&format!("{}\n\n\n9 0 0 0", SAMPLE)).unwrap();
+        assert_eq!(day.part1().to_string(), "1");
+        assert_eq!(day.part2().to_string(), "unsolved: the samples do not pin down opcode 9: it could be addi or mulr");
+    }
+}
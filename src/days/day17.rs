@@ -0,0 +1,56 @@
+use crate::{frames::Recorder, solution::{Answer, Solution}, water::Reservoir};
+use color_eyre::eyre::Result;
+
+/// Records the water spreading from the spring, one frame per fall or spread.
+pub fn record(input: &str, recorder: &mut Recorder) -> Result<usize> {
+    let mut reservoir = Reservoir::parse(input)?;
+    reservoir.fill_with(|r| recorder.capture(r));
+    recorder.finish(&reservoir)
+}
+
+pub struct Day17(Reservoir);
+
+impl Day17 {
+    fn filled(&self) -> Reservoir {
+        let mut reservoir = self.0.clone();
+        reservoir.fill();
+        reservoir
+    }
+}
+
+impl Solution for Day17 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day17(Reservoir::parse(input)?))
+    }
+    fn part1(&self) -> Answer {
+        self.filled().wet().into()
+    }
+    fn part2(&self) -> Answer {
+        self.filled().settled().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504";
+
+    #[test_case(EXAMPLE => "57")]
+    fn test_part1(input: &str) -> String {
+        Day17::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(EXAMPLE => "29")]
+    fn test_part2(input: &str) -> String {
+        Day17::parse(input).unwrap().part2().to_string()
+    }
+}
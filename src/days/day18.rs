@@ -0,0 +1,93 @@
+use crate::{cycle, grid::Grid, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+
+const OPEN: char = '.';
+const TREES: char = '|';
+const LUMBERYARD: char = '#';
+
+/// One minute of growth: open acres with three or more wooded neighbours
+/// fill with trees, trees next to three or more lumberyards are cut, and a
+/// lumberyard survives only next to both another lumberyard and trees.
+fn tick(area: &Grid<char>) -> Grid<char> {
+    Grid::from_fn(area.width(), area.height(), |pos| {
+        let count = |kind: char| area.neighbours8(pos).filter(|&n| area[n] == kind).count();
+        match area[pos] {
+            OPEN if count(TREES) >= 3 => TREES,
+            TREES if count(LUMBERYARD) >= 3 => LUMBERYARD,
+            LUMBERYARD if count(LUMBERYARD) == 0 || count(TREES) == 0 => OPEN,
+            acre => acre,
+        }
+    })
+}
+
+/// Wooded acres times lumberyards.
+fn resource_value(area: &Grid<char>) -> usize {
+    let count = |kind: char| area.iter().filter(|&(_, &acre)| acre == kind).count();
+    count(TREES) * count(LUMBERYARD)
+}
+
+/// The area after `minutes`; the states soon cycle, which skips the rest.
+fn after(area: &Grid<char>, minutes: u64) -> Grid<char> {
+    cycle::state_at(area.clone(), minutes, tick)
+}
+
+pub struct Day18(Grid<char>);
+
+impl Solution for Day18 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day18(Grid::parse(input, |c| matches!(c, OPEN | TREES | LUMBERYARD), "`.`, `|` or `#`")?))
+    }
+    fn part1(&self) -> Answer {
+        resource_value(&after(&self.0, 10)).into()
+    }
+    fn part2(&self) -> Answer {
+        resource_value(&after(&self.0, 1_000_000_000)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = ".#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.";
+
+    // Settles into a 28 minute cycle after 7 minutes; the example dies out.
+    const OSCILLATING: &str = "##.|.|||
+|||||#|#
+||..|#|#
+|.||#|.#
+..#####.
+.#|||..|
+..||#...
+|.|.||.#";
+
+    #[test_case(EXAMPLE => "1147")]
+    fn test_part1(input: &str) -> String {
+        Day18::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test]
+    fn test_cycle_matches_simulation() {
+        let area = Day18::parse(OSCILLATING).unwrap().0;
+        let mut simulated = area.clone();
+        for minutes in 1..=100 {
+            simulated = tick(&simulated);
+            assert_eq!(after(&area, minutes), simulated, "after {} minutes", minutes);
+        }
+    }
+
+    #[test]
+    fn test_rejects_unknown_acres() {
+        assert!(Day18::parse(".#x\n...").is_err());
+    }
+}
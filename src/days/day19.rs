@@ -0,0 +1,148 @@
+use crate::{
+    elfcode::{Machine, Program},
+    solution::{Answer, Solution},
+};
+use color_eyre::eyre::{ensure, Result};
+
+const REGISTERS: usize = 6;
+
+fn boot(machine: &Machine, r0: usize) -> Machine {
+    let mut machine = machine.clone();
+    machine.regs[0] = r0;
+    machine
+}
+
+/// The puzzle programs jump ahead to compute a target number, jump back
+/// and then sum its divisors into register 0 with two nested loops. The
+/// setup ends at the first backward jump, and the target is the largest
+/// register at that point.
+fn divisor_target(machine: &Machine, r0: usize) -> usize {
+    let mut machine = boot(machine, r0);
+    let mut jumped_ahead = false;
+    loop {
+        let before = machine.ip();
+        // An overflow ends the setup as surely as a halt does.
+        if !machine.step().unwrap_or(false) {
+            break;
+        }
+        jumped_ahead |= machine.ip() > before + 1;
+        if jumped_ahead && machine.ip() < before {
+            break;
+        }
+    }
+    machine.regs.into_iter().max().unwrap()
+}
+
+fn sum_of_divisors(n: usize) -> usize {
+    (1..).take_while(|i| i * i <= n)
+        .filter(|&i| n.is_multiple_of(i))
+        .map(|i| if i * i == n { i } else { i + n / i })
+        .sum()
+}
+
+fn parse_program(input: &str) -> Result<Machine> {
+    let program = Program::parse(input)?;
+    ensure!(program.ip_reg.is_some(), "expected an `#ip N` line before the program");
+    Machine::new(program, REGISTERS)
+}
+
+pub struct Day19(Machine);
+
+impl Solution for Day19 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day19(parse_program(input)?))
+    }
+    fn part1(&self) -> Answer {
+        let mut machine = boot(&self.0, 0);
+        match machine.run() {
+            Ok(()) => machine.regs[0].into(),
+            Err(_) => Answer::Unsolved,
+        }
+    }
+    fn part2(&self) -> Answer {
+        sum_of_divisors(divisor_target(&self.0, 1)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5";
+
+    // Shaped like the puzzle inputs: with r0 = 0 it sums the divisors of 879,
+    // with r0 = 1 those of 10551279.
+    const DIVISORS: &str = "#ip 4
+addi 4 16 4
+seti 1 5 1
+seti 1 2 2
+mulr 1 2 3
+eqrr 3 5 3
+addr 3 4 4
+addi 4 1 4
+addr 1 0 0
+addi 2 1 2
+gtrr 2 5 3
+addr 4 3 4
+seti 2 7 4
+addi 1 1 1
+gtrr 1 5 3
+addr 3 4 4
+seti 1 9 4
+mulr 4 4 4
+addi 5 2 5
+mulr 5 5 5
+mulr 4 5 5
+muli 5 11 5
+addi 3 1 3
+mulr 3 4 3
+addi 3 21 3
+addr 5 3 5
+addr 4 0 4
+seti 0 5 4
+setr 4 9 3
+mulr 3 4 3
+addr 4 3 3
+mulr 4 3 3
+muli 3 14 3
+mulr 3 4 3
+addr 5 3 5
+seti 0 9 0
+seti 0 6 4";
+
+    #[test_case(EXAMPLE => "6")]
+    #[test_case(DIVISORS => "1176")]
+    fn test_part1(input: &str) -> String {
+        Day19::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(0 => 879)]
+    #[test_case(1 => 10551279)]
+    fn test_divisor_target(r0: usize) -> usize {
+        divisor_target(&parse_program(DIVISORS).unwrap(), r0)
+    }
+
+    #[test]
+    fn test_shortcut_matches_simulation() {
+        let machine = parse_program(DIVISORS).unwrap();
+        let mut simulated = boot(&machine, 0);
+        simulated.run().unwrap();
+        assert_eq!(sum_of_divisors(divisor_target(&machine, 0)), simulated.regs[0]);
+    }
+
+    #[test_case("#ip 6\nseti 0 0 0")]
+    #[test_case("#ip 1\nmovr 0 0 0")]
+    #[test_case("#ip 1\naddr 0 7 0")]
+    #[test_case("seti 0 0 0")]
+    fn test_rejects(input: &str) {
+        assert!(parse_program(input).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+
+pub struct Day2(Vec<Vec<char>>);
+
+impl Solution for Day2 {
+    fn parse(input: &str) -> Result<Self> {
+        let boxes = parse::lines(input, |line| {
+            let id = line.trim();
+            match id.find(|c: char| !c.is_ascii_lowercase()) {
+                Some(idx) => {
+                    let bad = id[idx..].chars().next().unwrap();
+                    let bad = &id[idx..idx + bad.len_utf8()];
+                    Err(parse::error_at(line, bad, "box IDs may only contain `a`-`z`"))
+                }
+                None => Ok(id.chars().collect()),
+            }
+        })?;
+        Ok(Day2(boxes))
+    }
+    fn part1(&self) -> Answer {
+        let mut x = 0;
+        let mut y = 0;
+
+        for line in self.0.iter() {
+            let mut counts = [0u8; 26];
+            for &c in line {
+                counts[(c as u8 - b'a') as usize] += 1;
+            }
+
+            x += counts.contains(&2) as u32;
+            y += counts.contains(&3) as u32;
+        };
+        (x * y).into()
+    }
+    fn part2(&self) -> Answer {
+        let pair = self.0.iter()
+        .combinations(2)
+        .find(|pair| {
+            pair[0].iter()
+                .zip(pair[1])
+                .filter(|(a, b)| a != b)
+                .count() == 1
+        });
+        let same_chars: String = pair.map(|pair| {
+            pair[0].iter()
+                .zip(pair[1])
+                .filter_map(|(a, b)| if a == b { Some(*a) } else { None })
+                .collect()
+        }).unwrap_or_default();
+        same_chars.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab" => "12")]
+    fn test_part1(input: &str) -> String {
+        Day2::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz" => "fgij")]
+    fn test_part2(input: &str) -> String {
+        Day2::parse(input).unwrap().part2().to_string()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+use std::collections::HashMap;
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy)]
+struct Claim {
+    id: u32,
+    dis_left_edge: u32,
+    dis_top_edge: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Claim {
+    fn inches(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (self.dis_left_edge..(self.dis_left_edge + self.width)).flat_map(move |x| {
+            (self.dis_top_edge..(self.dis_top_edge + self.height)).map(move |y| (x, y))
+        })
+    }
+}
+
+pub struct Day3(Vec<Claim>);
+
+impl Solution for Day3 {
+    fn parse(input: &str) -> Result<Self> {
+        let re = Regex::new(r"^#(\d+) @ (\d+),(\d+): (\d+)x(\d+)$").unwrap();
+        let claims = parse::lines(input, |line| {
+            let caps = re.captures(line.trim())
+                .ok_or_else(|| parse::error_at(line, line, "expected `#id @ left,top: widthxheight`"))?;
+            let field = |idx: usize| parse::number(line, caps.get(idx).unwrap().as_str());
+            Ok(Claim {
+                id: field(1)?,
+                dis_left_edge: field(2)?,
+                dis_top_edge: field(3)?,
+                width: field(4)?,
+                height: field(5)?,
+            })
+        })?;
+        Ok(Day3(claims))
+    }
+    fn part1(&self) -> Answer {
+        let mut claims = HashMap::new();
+        for claim in self.0.iter() {
+            for inch in claim.inches() {
+                claims.entry(inch).and_modify(|e| *e += 1).or_insert(1);
+            }
+        }
+        claims.values().filter(|&&val| val > 1).count().into()
+    }
+    fn part2(&self) -> Answer {
+        let mut claims = HashMap::new();
+        let mut overlapping_ids: Vec<u32> = Vec::new();
+
+        for claim in self.0.iter() {
+            let id = claim.id;
+            for inch in claim.inches() {
+                claims.entry(inch)
+                    .and_modify(|e: &mut Vec<u32>| {
+                        e.push(id);
+                        if e.len() > 1 {
+                            overlapping_ids.extend(e.iter().copied());
+                        }
+                    })
+                    .or_insert_with(|| vec![id]);
+            }
+        }
+
+        overlapping_ids.sort();
+        overlapping_ids.dedup();
+
+        let non_overlapping_id = self.0.iter()
+            .map(|claim| claim.id)
+            .find(|id| !overlapping_ids.contains(id));
+
+        match non_overlapping_id {
+            Some(id) => id.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+    #[test_case(EXAMPLE => "4")]
+    fn test_part1(input: &str) -> String {
+        Day3::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(EXAMPLE => "3")]
+    #[test_case("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4" => "unsolved"; "every claim overlaps")]
+    #[test_case("" => "unsolved"; "no claims")]
+    fn test_part2(input: &str) -> String {
+        Day3::parse(input).unwrap().part2().to_string()
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::{ensure, Result};
+use std::collections::HashMap;
+use itertools::Itertools;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Guard {
+    number: u32,
+    time_asleep: Vec<u32>,
+    time_awake: Vec<u32>,
+    sleep_ranges: Vec<Range<u32>>,
+}
+impl Guard {
+    fn clear(&mut self) {
+        self.number = 0;
+        self.time_asleep.clear();
+        self.time_awake.clear();
+    }
+}
+struct PrettyVec(Vec<Guard>);
+
+impl fmt::Display for PrettyVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for guard in &self.0 {
+            writeln!(f, "Number: {}", guard.number)?;
+            writeln!(f, "Time Asleep: {:?}", guard.time_asleep)?;
+            writeln!(f, "Time Awake: {:?}", guard.time_awake)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+fn sort_keys_part2(map: HashMap<u32, u32>) -> Option<(u32, u32)> {
+    map.into_iter()
+       .map(|(k, v)| (std::cmp::Reverse(v), k))
+       .sorted_unstable()
+       .next()
+       .map(|(std::cmp::Reverse(v), k)| (v, k))
+}
+
+fn sort_keys_part1(map: HashMap<u32, u32>) -> Option<u32> {
+    map.into_iter()
+       .map(|(k, v)| (std::cmp::Reverse(v), k))
+       .sorted_unstable()
+       .next()
+       .map(|(_k, v)| v)
+}
+fn find_the_guard(vec: &[Guard]) -> (u32, Vec<Range<u32>>) {
+    let mut longest_sleep_time = 0;
+    let mut number = 0;
+    let mut ranges = vec![];
+
+    for guard in vec.iter() {
+        let sum: u32 = guard.time_asleep.iter().sum();
+        if sum > longest_sleep_time {
+            longest_sleep_time = sum;
+            number = guard.number;
+            ranges.clear();
+            ranges.extend(guard.sleep_ranges.clone());
+        }
+    }
+    (number, ranges)
+}
+
+fn sort_lines(vec: &mut [String]) {
+    vec.sort_by(|a, b| {
+        let num_a: i32 = a[0..8].parse().unwrap();
+        let num_b: i32 = b[0..8].parse().unwrap();
+        num_a.cmp(&num_b)
+    });
+}
+
+// Checks one `[1518-MM-DD HH:MM] event` record so the munging below can't panic.
+fn check_record(line: &str) -> Result<String> {
+    let (stamp, event) = parse::split_once(line, line.trim(), "] ")?;
+    let stamp = parse::strip_prefix(line, stamp, "[1518-")?;
+    let (date, time) = parse::split_once(line, stamp, " ")?;
+    let (month, day) = parse::split_once(line, date, "-")?;
+    let (hour, minute) = parse::split_once(line, time, ":")?;
+    for field in [month, day, hour, minute] {
+        if field.len() != 2 {
+            return Err(parse::error_at(line, field, "expected two digits"));
+        }
+        parse::number::<u32>(line, field)?;
+    }
+    match event {
+        "falls asleep" | "wakes up" => {}
+        _ => {
+            let number = parse::strip_prefix(line, event, "Guard #")?;
+            let number = parse::strip_suffix(line, number, " begins shift")?;
+            parse::number::<u32>(line, number)?;
+        }
+    }
+    Ok(line.trim().strip_prefix("[1518-").unwrap().replace("-", "").replace(":", "").replace(" ", ""))
+}
+
+/// Checks the checked records in time order: the log starts with a shift,
+/// someone naps, every nap ends before the next one or the next shift, and
+/// guards only fall asleep and wake up during the midnight hour.
+fn check_order(lines: &[String]) -> Result<()> {
+    ensure!(!lines.is_empty(), "the log is empty");
+    ensure!(lines.iter().any(|line| line.ends_with("fallsasleep")), "no guard ever falls asleep");
+    let (mut on_shift, mut asleep) = (false, false);
+    for line in lines {
+        let at = format!("{}-{} {}:{}", &line[0..2], &line[2..4], &line[4..6], &line[6..8]);
+        if !line.ends_with("beginsshift") {
+            ensure!(&line[4..6] == "00", "a guard falls asleep or wakes up at {}, outside the midnight hour", at);
+        }
+        if line.ends_with("beginsshift") {
+            ensure!(!asleep, "a shift begins at {} while the last guard is still asleep", at);
+            on_shift = true;
+        } else if line.ends_with("fallsasleep") {
+            ensure!(on_shift, "a guard falls asleep at {} before any shift begins", at);
+            ensure!(!asleep, "the guard falls asleep again at {} without waking up", at);
+            asleep = true;
+        } else {
+            ensure!(asleep, "the guard wakes up at {} without having fallen asleep", at);
+            asleep = false;
+        }
+    }
+    ensure!(!asleep, "the last guard never wakes up");
+    Ok(())
+}
+
+fn find_unique_guards(input: &str) -> Result<Vec<Guard>> {
+    let mut lines: Vec<String> = parse::lines(input, check_record)?;
+    sort_lines(&mut lines);
+    check_order(&lines)?;
+    let mut lines: Vec<String> = lines.iter_mut().map(|line| {
+        let mut mod_line = String::from(&line[4..]);
+        if mod_line.starts_with("23") {
+            mod_line.replace_range(0..4, "0000")
+        }
+        
+        String::from(&mod_line[2..])
+    }).collect();
+    lines.push("End of list".to_string());
+    let mut guards: Vec<Guard> = Vec::new();
+    let mut guard = Guard {
+        number: 0,
+        time_asleep: vec![],
+        time_awake: vec![],
+        sleep_ranges: vec![],
+    };
+    for line in lines.iter() {
+        if line.ends_with("beginsshift") {
+            if !guard.time_asleep.is_empty() {
+                guard.time_asleep.push(59);
+                guards.push(guard.clone());
+            }
+            guard.clear();
+            let (_num, rest) = line.split_once("]").unwrap();
+            guard.time_awake.push(0);
+            let number = rest.strip_prefix("Guard#").unwrap().strip_suffix("beginsshift").unwrap().parse::<u32>().unwrap();
+            guard.number += number;
+        }
+        if line.ends_with("fallsasleep") {
+            let (num, _) = line.split_once("]").unwrap();
+            guard.time_asleep.push(num.parse::<u32>().unwrap());
+        }
+        if line.ends_with("wakesup") {
+            let (num, _) = line.split_once("]").unwrap();
+            guard.time_awake.push(num.parse::<u32>().unwrap());
+        }
+        if line == "End of list" {
+            guard.time_asleep.push(59);
+            guards.push(guard.clone());
+            break;
+        }
+    }
+    for guard in &mut guards {
+        let mut vec_clone = guard.time_awake.clone();
+        vec_clone.remove(0);
+        let mut interleave_iter = guard.time_asleep.iter().cloned().interleave(vec_clone.iter().cloned());
+        let mut vec_range: Vec<Range<u32>> = Vec::new();
+
+        while let (Some(start), Some(end)) = (interleave_iter.next(), interleave_iter.next()) {
+            vec_range.push(start..end);
+        }
+        guard.sleep_ranges.extend(vec_range);
+    }
+    for guard in &mut guards {
+        let vec: Vec<u32> = guard
+        .time_awake
+        .clone()
+        .into_iter()
+        .interleave(guard.time_asleep.clone())
+        .collect::<Vec<u32>>()
+        .windows(2)
+        .map(|window| window[1] - window[0])
+        .collect();
+        guard.time_awake.clear();
+        guard.time_asleep.clear();
+        
+        for (idx, &time) in vec.iter().enumerate() {
+            if idx % 2 == 0 {
+                guard.time_awake.push(time);
+            } else {
+                guard.time_asleep.push(time);
+            }
+        }
+    }
+    let mut guard_map: HashMap<u32, Guard> = HashMap::new();
+
+    for guard in guards {
+        guard_map
+            .entry(guard.number)
+            .and_modify(|existing: &mut Guard| {
+                existing.time_asleep.extend(guard.time_asleep.clone());
+                existing.time_awake.extend(guard.time_awake.clone());
+                existing.sleep_ranges.extend(guard.sleep_ranges.clone());
+            })
+            .or_insert(guard);
+    }
+    let unique_guards: Vec<Guard> = guard_map.into_values().collect();
+    Ok(unique_guards)
+}
+pub struct Day4(Vec<Guard>);
+
+impl Solution for Day4 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day4(find_unique_guards(input)?))
+    }
+    fn part1(&self) -> Answer {
+        let (number, ranges) = find_the_guard(&self.0);
+        let mut range_map = HashMap::new();
+        for range in ranges.into_iter() {
+            for el in range {
+                *range_map.entry(el).or_insert(0) += 1;
+            }
+        }
+        match sort_keys_part1(range_map) {
+            Some(minute) => (number * minute).into(),
+            None => Answer::Unsolved,
+        }
+    }
+    fn part2(&self) -> Answer {
+        let mut num_of_times = 0;
+        let mut number = 0;
+        let mut minute_asleep = 0;
+        for guard in self.0.iter() {
+            let mut range_map = HashMap::new();
+            for range in guard.sleep_ranges.iter().cloned() {
+                for el in range {
+                    *range_map.entry(el).or_insert(0) += 1;
+                }
+            }
+            // Guards who never nap have no favourite minute.
+            let Some((freq, minute)) = sort_keys_part2(range_map) else { continue };
+            if num_of_times < freq {
+                num_of_times = freq;
+                minute_asleep = minute;
+                number = guard.number;
+            }
+        }
+        (number * minute_asleep).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+    #[test_case(EXAMPLE => "240")]
+    fn test_part1(input: &str) -> String {
+        Day4::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(EXAMPLE => "4455")]
+    #[test_case(&format!("{}\n[1518-11-06 00:00] Guard #7 begins shift", EXAMPLE) => "4455"; "last guard never naps")]
+    fn test_part2(input: &str) -> String {
+        Day4::parse(input).unwrap().part2().to_string()
+    }
+
+    #[test_case("" => "the log is empty")]
+    #[test_case("[1518-11-01 00:05] falls asleep\n[1518-11-01 00:25] wakes up" => "a guard falls asleep at 11-01 00:05 before any shift begins")]
+    #[test_case("[1518-11-01 00:00] Guard #10 begins shift" => "no guard ever falls asleep")]
+    #[test_case("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up" => "no guard ever falls asleep")]
+    #[test_case("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep" => "the last guard never wakes up")]
+    #[test_case("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:07] falls asleep" => "the guard falls asleep again at 11-01 00:07 without waking up")]
+    #[test_case("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:50] falls asleep\n[1518-11-01 01:10] wakes up" => "a guard falls asleep or wakes up at 11-01 01:10, outside the midnight hour")]
+    fn test_rejects_out_of_order(input: &str) -> String {
+        Day4::parse(input).err().unwrap().to_string()
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+
+fn form(vec: Vec<u8>) -> Vec<u8> {
+    let mut result = Vec::new();
+    let mut iter = vec.iter().peekable();
+
+    while let Some(&current) = iter.next() {
+        if let Some(&&next) = iter.peek() {
+            if (current as i16 - next as i16).abs() == 32 {
+                iter.next();
+                continue;
+            }
+        }
+        result.push(current);
+    }
+
+    if result.len() == vec.len() {
+        result
+    } else {
+        form(result)
+    }
+}
+
+fn remove_type(vec: Vec<u8>) -> usize {
+    (b'a'..=b'z')
+        .map(|c| {
+            let filtered: Vec<u8> = vec
+                .iter()
+                .filter(|&&x| x != c && x != c - 32)
+                .copied()
+                .collect();
+            form(filtered).len()
+        })
+        .min()
+        .unwrap()
+}
+
+pub struct Day5(Vec<u8>);
+
+impl Solution for Day5 {
+    fn parse(input: &str) -> Result<Self> {
+        let polymer = input.trim();
+        if let Some(idx) = polymer.find(|c: char| !c.is_ascii_alphabetic()) {
+            let bad = polymer[idx..].chars().next().unwrap();
+            let bad = &polymer[idx..idx + bad.len_utf8()];
+            return Err(parse::error_at(polymer, bad, "polymer units must be letters"));
+        }
+        Ok(Day5(polymer.bytes().collect()))
+    }
+    fn part1(&self) -> Answer {
+        form(self.0.clone()).len().into()
+    }
+    fn part2(&self) -> Answer {
+        remove_type(self.0.clone()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("aA" => 0)]
+    #[test_case("abBA" => 0)]
+    #[test_case("abAB" => 4)]
+    #[test_case("aabAAB" => 6)]
+    #[test_case("dabAcCaCBAcCcaDA" => 10)]
+    fn test_form(polymer: &str) -> usize {
+        form(polymer.bytes().collect()).len()
+    }
+
+    #[test_case("dabAcCaCBAcCcaDA" => 4)]
+    fn test_remove_type(polymer: &str) -> usize {
+        remove_type(polymer.bytes().collect())
+    }
+}
@@ -0,0 +1,151 @@
+// This is not my solution, my solution passed the test but didn't give the right output with the main input
+use crate::{geometry::Point, parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
+use std::collections::{HashSet, HashMap};
+
+/// Points exactly `step` away from `center`.
+fn radar(center: Point, step: i32) -> impl Iterator<Item=Point> {
+    (center.x - step..=center.x + step)
+        .flat_map(move |x| {
+            (center.y - step..=center.y + step)
+                .map(move |y| Point { x, y })
+        })
+        .filter(move |&cord| center.manhattan(cord) == step)
+}
+
+struct Map {
+    locations: Vec<Point>,
+    finite_locations: HashSet<Point>,
+    grid: HashMap<Point, Point>,
+}
+
+impl Map {
+    fn new(locations: Vec<Point>) -> Self {
+        Self {
+            finite_locations: HashSet::new(),
+            locations,
+            grid: HashMap::new(),
+        }
+    }
+    fn search_for_finite_location(&mut self) {
+        for step in 0..100 {
+            for location in &self.locations {
+                if self.finite_locations.contains(location) {
+                    continue;
+                }
+
+                for cord in radar(*location, step) {
+                    let closest = match self.closest_location(cord) {
+                        None => continue,
+                        Some(closest) => closest,
+                    };
+
+                    self.grid.insert(cord, closest);
+                }
+            }
+
+            for &cord in &self.locations {
+                if !radar(cord, step).any(|c| self.grid.get(&c) == Some(&cord)) {
+                    self.finite_locations.insert(cord);
+                }
+            }
+        }
+    }
+
+    fn closest_location(&self, cord: Point) -> Option<Point> {
+        let (mut min, mut unique) = (self.locations[0], true);
+
+        for &location in &self.locations[1..] {
+            if location.manhattan(cord) == min.manhattan(cord) {
+                unique = false
+            } else if location.manhattan(cord) < min.manhattan(cord) {
+                min = location;
+                unique = true;
+            }
+        }
+        if !unique {
+            None
+        } else {
+            Some(min)
+        }
+    }
+
+    fn distance_sum(&self, cord: Point) -> i32 {
+        self.locations
+            .iter()
+            .map(|&loc| loc.manhattan(cord))
+            .sum()
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Point>> {
+    parse::lines(input, |line| {
+        let (x, y) = parse::split_once(line, line, ", ")?;
+        Ok(Point {
+            x: parse::number(line, x)?,
+            y: parse::number(line, y)?,
+        })
+    })
+}
+/// Number of points whose total distance to every location is below `limit`.
+fn safe_region(locations: &[Point], limit: i32) -> usize {
+    let map = Map::new(locations.to_vec());
+    let range = 400;
+    let mut size = 0;
+
+    for x in -range..=range {
+        for y in -range..=range {
+            if map.distance_sum(Point { x, y }) < limit {
+                size += 1;
+            }
+        }
+    }
+    size
+}
+
+pub struct Day6(Vec<Point>);
+
+impl Solution for Day6 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day6(parse_input(input)?))
+    }
+    fn part1(&self) -> Answer {
+        let mut map = Map::new(self.0.clone());
+        map.search_for_finite_location();
+
+        let mut biggest_area = 0;
+        for &cord in &map.finite_locations {
+            let mut possible = 0;
+            for &cord2 in map.grid.values() {
+                if cord == cord2 {
+                    possible += 1;
+                }
+            }
+            if possible > biggest_area {
+                biggest_area = possible;
+            }
+        }
+        biggest_area.into()
+    }
+    fn part2(&self) -> Answer {
+        safe_region(&self.0, 10_000).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9";
+
+    #[test_case(EXAMPLE => "17")]
+    fn test_part1(input: &str) -> String {
+        Day6::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(EXAMPLE, 32 => 16)]
+    fn test_safe_region(input: &str, limit: i32) -> usize {
+        safe_region(&parse_input(input).unwrap(), limit)
+    }
+}
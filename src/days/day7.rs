@@ -1,23 +1,27 @@
 // The solution to part 1 is mine, the solution to part 2 I found on Github
 
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::Result;
 use std::collections::{HashSet, BTreeSet, HashMap};
 use itertools::Itertools;
 use nom::{
     sequence::tuple,
     bytes::complete::tag,
-    character::complete::alpha1,
+    character::complete::satisfy,
+    combinator::all_consuming,
     IResult,
 };
 fn parse_ins(s: &str) -> IResult<&str, (char, char)> {
-    let mut parser = tuple((
+    let step = || satisfy(|c| c.is_ascii_uppercase());
+    let mut parser = all_consuming(tuple((
         tag("Step "),
-        alpha1,
+        step(),
         tag(" must be finished before step "),
-        alpha1,
+        step(),
         tag(" can begin."),
-    ));
+    )));
     let (remainder, (_, char1, _, char2, _)) = parser(s)?;
-    Ok((remainder, (char1.chars().next().unwrap(), char2.chars().next().unwrap())))
+    Ok((remainder, (char1, char2)))
 }
 fn process(unav: &mut BTreeSet<char>, av: &mut BTreeSet<char>, steps: &mut HashMap<char, Vec<char>>, order: &mut Vec<char>) {
     av.retain(|&x| !unav.contains(&x));
@@ -27,7 +31,7 @@ fn process(unav: &mut BTreeSet<char>, av: &mut BTreeSet<char>, steps: &mut HashM
         if let Some(value) = steps.remove(&next) {
             for v in value.iter() {
                 if !steps.values().flat_map(|vec| vec.iter()).contains(v) {
-                    unav.remove(&v);
+                    unav.remove(v);
                     av.insert(*v);
                 }
             }
@@ -37,17 +41,15 @@ fn process(unav: &mut BTreeSet<char>, av: &mut BTreeSet<char>, steps: &mut HashM
         process(unav, av, steps, order);
     }
 }
-fn part1(input: &str) -> String {
+fn find_order(dependencies: &[Dependency]) -> String {
     let mut steps = HashMap::new();
     let mut order: Vec<char> = Vec::new();
     let mut unavailable = BTreeSet::new();
     let mut available = BTreeSet::new();
-    for line in input.lines() {
-        if let Ok((_, (char1, char2))) = parse_ins(line) {
-            available.insert(char1);
-            unavailable.insert(char2);
-            steps.entry(char1).or_insert(Vec::new()).push(char2);
-        }
+    for &Dependency { step, required } in dependencies {
+        available.insert(required);
+        unavailable.insert(step);
+        steps.entry(required).or_insert(Vec::new()).push(step);
     }
     process(&mut unavailable, &mut available, &mut steps, &mut order);
     order.into_iter().collect::<String>()
@@ -56,7 +58,7 @@ fn part1(input: &str) -> String {
 type Step = char;
 type Required = HashMap<Step, HashSet<Step>>;
 
-#[derive(Debug, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 struct Dependency {
     step: Step,
     required: Step,
@@ -89,17 +91,17 @@ enum Status {
     },
 }
 
-fn apply_tick(workers: &mut Vec<Status>) -> Vec<Step> {
+fn apply_tick(workers: &mut [Status]) -> Vec<Step> {
     let mut finished_steps: Vec<Step> = Vec::new();
 
-    for index in 0..workers.len() {
-        match workers[index] {
+    for worker in workers.iter_mut() {
+        match worker {
             Status::Idle => {}
-            Status::Working { step, ref mut remaining } => {
+            Status::Working { step, remaining } => {
                 *remaining -= 1;
                 if *remaining == 0 {
-                    finished_steps.push(step);
-                    workers[index] = Status::Idle;
+                    finished_steps.push(*step);
+                    *worker = Status::Idle;
                 }
             }
         }
@@ -107,14 +109,13 @@ fn apply_tick(workers: &mut Vec<Status>) -> Vec<Step> {
 
     finished_steps
 }
-fn all_workers_idle(workers: &Vec<Status>) -> bool {
+fn all_workers_idle(workers: &[Status]) -> bool {
     workers.iter().all(|&w| w == Status::Idle)
 }
-fn available_workers(workers: &Vec<Status>) -> Vec<usize> {
+fn available_workers(workers: &[Status]) -> Vec<usize> {
     let mut available: Vec<usize> = Vec::new();
 
-    for index in 0..workers.len() {
-        let worker = workers[index];
+    for (index, &worker) in workers.iter().enumerate() {
         if worker == Status::Idle {
             available.push(index);
         }
@@ -123,20 +124,13 @@ fn available_workers(workers: &Vec<Status>) -> Vec<usize> {
     available
 }
 
-fn compute_step_time(step: &Step) -> u32 {
-    (*step as u32) - b'A' as u32 + 1 + 60
+fn compute_step_time(step: &Step, base: u32) -> u32 {
+    (*step as u32) - b'A' as u32 + 1 + base
 }
 
-fn part2(input: &str) -> u32 {
-    let dependencies: Result<Vec<Dependency>, _> = input
-    .lines()
-    .map(|line| {
-        parse_ins(line).map(|(_, (char1, char2))| Dependency { step: char2, required: char1 })
-    })
-    .collect();
-
-    let dependencies = dependencies.unwrap();
-
+/// Seconds `workers` elves take to assemble everything when each step
+/// takes `base` seconds plus its position in the alphabet.
+fn assemble(dependencies: &[Dependency], workers: usize, base: u32) -> u32 {
     let mut requirements: Required = HashMap::new();
     dependencies
         .iter()
@@ -145,7 +139,7 @@ fn part2(input: &str) -> u32 {
             requirements.entry(dep.required).or_default();
         });
     let mut assigned: HashSet<Step> = HashSet::new();
-    let mut workers: Vec<Status> = vec![Status::Idle; 5];
+    let mut workers: Vec<Status> = vec![Status::Idle; workers];
     let mut done: HashSet<Step> = HashSet::new();
     let mut seconds: u32 = 0;
     loop {
@@ -162,7 +156,7 @@ fn part2(input: &str) -> u32 {
             };
             workers[index] = Status::Working {
                 step,
-                remaining: compute_step_time(&step),
+                remaining: compute_step_time(&step, base),
             };
             assigned.insert(step);
         }
@@ -171,7 +165,45 @@ fn part2(input: &str) -> u32 {
     seconds
 }
 
-fn main() {
-    let input = include_str!("input7.txt");
-    println!("{}, {}", part1(input), part2(input));
-}
\ No newline at end of file
+pub struct Day7(Vec<Dependency>);
+
+impl Solution for Day7 {
+    fn parse(input: &str) -> Result<Self> {
+        let dependencies = parse::lines(input, |line| {
+            let expected = "`Step X must be finished before step Y can begin.`";
+            let (char1, char2) = parse::nom(line, parse_ins(line.trim()), expected)?;
+            Ok(Dependency { step: char2, required: char1 })
+        })?;
+        Ok(Day7(dependencies))
+    }
+    fn part1(&self) -> Answer {
+        find_order(&self.0).into()
+    }
+    fn part2(&self) -> Answer {
+        assemble(&self.0, 5, 60).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    #[test_case(EXAMPLE => "CABDFE")]
+    fn test_part1(input: &str) -> String {
+        Day7::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(EXAMPLE, 2, 0 => 15)]
+    fn test_assemble(input: &str, workers: usize, base: u32) -> u32 {
+        assemble(&Day7::parse(input).unwrap().0, workers, base)
+    }
+}
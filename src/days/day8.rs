@@ -1,3 +1,5 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::{ensure, eyre, Result};
 use std::rc::Rc;
 use std::cell::RefCell;
 
@@ -67,48 +69,75 @@ impl Node {
             println!("{}No metadata entries", indent);
         }
     }
-    fn make_tree(&mut self, data: &[u32], start: usize) -> usize {
+    fn make_tree(&mut self, data: &[u32], start: usize) -> Result<usize> {
         let mut current_index = start;
 
         // Process each child node
         for _ in 0..self.header.idx_c {
-            let child_idx_c = data[current_index];
-            let child_idx_m = data[current_index + 1];
+            let header = read_header(data, current_index)?;
             current_index += 2;
 
-            let mut child_node = Node::new(Header::new(child_idx_c, child_idx_m));
-            current_index = child_node.make_tree(data, current_index);
+            let mut child_node = Node::new(header);
+            current_index = child_node.make_tree(data, current_index)?;
             self.children.push(Rc::new(RefCell::new(child_node)));
         }
 
         // Assign metadata entries
-        self.entries.extend_from_slice(&data[current_index..current_index + self.header.idx_m as usize]);
-        current_index += self.header.idx_m as usize;
+        let end = current_index + self.header.idx_m as usize;
+        let entries = data.get(current_index..end).ok_or_else(|| {
+            eyre!("tree ends early: a node needs metadata entries {}..={} but there are only {} numbers", current_index + 1, end, data.len())
+        })?;
+        self.entries.extend_from_slice(entries);
 
-        current_index
+        Ok(end)
     }
 }
 
-fn create_tree(input: &str) -> Node {
-    let num: Vec<u32> = input.split_whitespace().filter_map(|x| x.parse::<u32>().ok()).collect();
-    let idx_c = num[0];
-    let idx_m = num[1];
-    let header = Header::new(idx_c, idx_m);
-    let mut root = Node::new(header);
+fn read_header(data: &[u32], at: usize) -> Result<Header> {
+    match data.get(at..at + 2) {
+        Some(&[children, entries]) => Ok(Header::new(children, entries)),
+        _ => Err(eyre!("tree ends early: expected a node header at number {}", at + 1)),
+    }
+}
 
-    root.make_tree(&num, 2);
-    root
+fn create_tree(input: &str) -> Result<Node> {
+    let num: Vec<u32> = parse::lines(input, |line| {
+        line.split_whitespace().map(|x| parse::number(line, x)).collect::<Result<Vec<u32>>>()
+    })?.concat();
+    let mut root = Node::new(read_header(&num, 0)?);
+
+    let end = root.make_tree(&num, 2)?;
+    ensure!(end == num.len(), "{} numbers left over after the root node", num.len() - end);
+    Ok(root)
 }
-fn part1(input: &str) -> u32 {
-    let root = create_tree(input);
-    root.sum_entries()
+pub struct Day8(Node);
+
+impl Solution for Day8 {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Day8(create_tree(input)?))
+    }
+    fn part1(&self) -> Answer {
+        self.0.sum_entries().into()
+    }
+    fn part2(&self) -> Answer {
+        self.0.find_value().into()
+    }
 }
-fn part2(input: &str) -> u32 {
-    let root = create_tree(input);
-    root.print_tree(1);
-    root.find_value()
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test_case(EXAMPLE => "138")]
+    fn test_part1(input: &str) -> String {
+        Day8::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test_case(EXAMPLE => "66")]
+    fn test_part2(input: &str) -> String {
+        Day8::parse(input).unwrap().part2().to_string()
+    }
 }
-fn main() {
-    let input = include_str!("input8.txt");
-    println!("{}", part2(input));
-}
\ No newline at end of file
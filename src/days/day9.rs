@@ -0,0 +1,73 @@
+use crate::{parse, solution::{Answer, Solution}};
+use color_eyre::eyre::{eyre, Result};
+use std::collections::VecDeque;
+
+fn high_score(players: usize, marbles: u64) -> u64 {
+    let mut curr_player = 1;
+    let mut players = vec![0u64; players];
+    let mut circle: VecDeque<u64> = (0..2).collect();
+    for curr_marble in 2..=marbles {
+        curr_player += 1;
+        let curr_player_idx = curr_player % players.len();
+        if curr_marble % 23 == 0 {
+            circle.rotate_right(7);
+            let removed = circle.pop_front().unwrap();
+            players[curr_player_idx] += curr_marble + removed;
+            continue;
+        }
+        circle.rotate_left(2);
+        circle.push_front(curr_marble);
+    }
+    players.into_iter().max().unwrap()
+}
+fn parse_game(line: &str) -> Result<(usize, u32)> {
+    let (players, rest) = parse::split_once(line, line.trim(), " players; last marble is worth ")?;
+    let marbles = parse::strip_suffix(line, rest, " points")?;
+    let count = parse::number(line, players)?;
+    if count == 0 {
+        return Err(parse::error_at(line, players, "a game needs at least one player"));
+    }
+    Ok((count, parse::number(line, marbles)?))
+}
+pub struct Day9 {
+    players: usize,
+    marbles: u32,
+}
+
+impl Solution for Day9 {
+    fn parse(input: &str) -> Result<Self> {
+        let games = parse::lines(input, parse_game)?;
+        let &[(players, marbles)] = games.as_slice() else {
+            return Err(eyre!("expected a single game description, found {}", games.len()));
+        };
+        Ok(Day9 { players, marbles })
+    }
+    fn part1(&self) -> Answer {
+        high_score(self.players, self.marbles.into()).into()
+    }
+    fn part2(&self) -> Answer {
+        high_score(self.players, u64::from(self.marbles) * 100).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("9 players; last marble is worth 25 points" => "32")]
+    #[test_case("10 players; last marble is worth 1618 points" => "8317")]
+    #[test_case("13 players; last marble is worth 7999 points" => "146373")]
+    #[test_case("17 players; last marble is worth 1104 points" => "2764")]
+    #[test_case("21 players; last marble is worth 6111 points" => "54718")]
+    #[test_case("30 players; last marble is worth 5807 points" => "37305")]
+    fn test_part1(input: &str) -> String {
+        Day9::parse(input).unwrap().part1().to_string()
+    }
+
+    #[test]
+    fn test_rejects_no_players() {
+        let err = Day9::parse("0 players; last marble is worth 25 points").err().unwrap();
+        assert!(format!("{:?}", err).contains("a game needs at least one player"), "{:?}", err);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::solution::Solution;
+use color_eyre::eyre::Result;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+
+// Inputs are only compiled in with the `embedded-inputs` feature, so a missing
+// file under `inputs/` never breaks the build.
+#[cfg(feature = "embedded-inputs")]
+macro_rules! embedded {
+    ($file:literal) => {
+        Some(include_str!(concat!("../../inputs/", $file)))
+    };
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+macro_rules! embedded {
+    ($file:literal) => {
+        None
+    };
+}
+
+/// A registered puzzle and, optionally, its embedded input.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    pub input: Option<&'static str>,
+    pub parse: fn(&str) -> Result<Box<dyn Solution>>,
+}
+
+fn boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        title: "Chronal Calibration",
+        input: embedded!("day01.txt"),
+        parse: boxed::<day1::Day1>,
+    },
+    Day {
+        day: 2,
+        title: "Inventory Management System",
+        input: embedded!("day02.txt"),
+        parse: boxed::<day2::Day2>,
+    },
+    Day {
+        day: 3,
+        title: "No Matter How You Slice It",
+        input: embedded!("day03.txt"),
+        parse: boxed::<day3::Day3>,
+    },
+    Day {
+        day: 4,
+        title: "Repose Record",
+        input: embedded!("day04.txt"),
+        parse: boxed::<day4::Day4>,
+    },
+    Day {
+        day: 5,
+        title: "Alchemical Reduction",
+        input: embedded!("day05.txt"),
+        parse: boxed::<day5::Day5>,
+    },
+    Day {
+        day: 6,
+        title: "Chronal Coordinates",
+        input: embedded!("day06.txt"),
+        parse: boxed::<day6::Day6>,
+    },
+    Day {
+        day: 7,
+        title: "The Sum of Its Parts",
+        input: embedded!("day07.txt"),
+        parse: boxed::<day7::Day7>,
+    },
+    Day {
+        day: 8,
+        title: "Memory Maneuver",
+        input: embedded!("day08.txt"),
+        parse: boxed::<day8::Day8>,
+    },
+    Day {
+        day: 9,
+        title: "Marble Mania",
+        input: embedded!("day09.txt"),
+        parse: boxed::<day9::Day9>,
+    },
+    Day {
+        day: 10,
+        title: "The Stars Align",
+        input: embedded!("day10.txt"),
+        parse: boxed::<day10::Day10>,
+    },
+    Day {
+        day: 11,
+        title: "Chronal Charge",
+        input: embedded!("day11.txt"),
+        parse: boxed::<day11::Day11>,
+    },
+    Day {
+        day: 12,
+        title: "Subterranean Sustainability",
+        input: embedded!("day12.txt"),
+        parse: boxed::<day12::Day12>,
+    },
+    Day {
+        day: 13,
+        title: "Mine Cart Madness",
+        input: embedded!("day13.txt"),
+        parse: boxed::<day13::Day13>,
+    },
+    Day {
+        day: 14,
+        title: "Chocolate Charts",
+        input: embedded!("day14.txt"),
+        parse: boxed::<day14::Day14>,
+    },
+    Day {
+        day: 15,
+        title: "Beverage Bandits",
+        input: embedded!("day15.txt"),
+        parse: boxed::<day15::Day15>,
+    },
+    Day {
+        day: 16,
+        title: "Chronal Classification",
+        input: embedded!("day16.txt"),
+        parse: boxed::<day16::Day16>,
+    },
+    Day {
+        day: 17,
+        title: "Reservoir Research",
+        input: embedded!("day17.txt"),
+        parse: boxed::<day17::Day17>,
+    },
+    Day {
+        day: 18,
+        title: "Settlers of The North Pole",
+        input: embedded!("day18.txt"),
+        parse: boxed::<day18::Day18>,
+    },
+    Day {
+        day: 19,
+        title: "Go With The Flow",
+        input: embedded!("day19.txt"),
+        parse: boxed::<day19::Day19>,
+    },
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,290 @@
+use crate::elfcode::Machine;
+use color_eyre::eyre::{bail, ensure, eyre, Result, WrapErr};
+use std::{collections::BTreeSet, fmt, io::Write};
+
+/// Why [`Debugger::step`] or [`Debugger::cont`] handed control back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The single step asked for has run.
+    Stepped,
+    Halted,
+    /// The next instruction to run has a breakpoint.
+    Breakpoint(usize),
+    /// The instruction at `ip` changed a watched register.
+    Watchpoint { ip: usize, reg: usize, old: usize, new: usize },
+    /// The step budget ran out.
+    Budget,
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Stepped => write!(f, "stepped"),
+            Stop::Halted => write!(f, "halted"),
+            Stop::Breakpoint(ip) => write!(f, "breakpoint at {}", ip),
+            Stop::Watchpoint { ip, reg, old, new } => write!(f, "r{} changed {} -> {} at {}", reg, old, new, ip),
+            Stop::Budget => write!(f, "step budget used up"),
+        }
+    }
+}
+
+/// Writes one line per executed instruction, in the puzzle's
+/// `ip=N [regs] ins [regs]` style, until `limit` lines are written.
+struct Trace {
+    out: Box<dyn Write>,
+    limit: u64,
+    written: u64,
+}
+
+impl Trace {
+    fn record(&mut self, step: u64, ip: usize, before: &[usize], line: &str, after: &[usize]) -> Result<()> {
+        if self.written < self.limit {
+            writeln!(self.out, "{} ip={} {:?} {} {:?}", step, ip, before, line, after)?;
+        } else if self.written == self.limit {
+            writeln!(self.out, "trace capped at {} steps", self.limit)?;
+            self.out.flush()?;
+        }
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// Runs a [`Machine`] under breakpoints and register watchpoints, counting
+/// how often each instruction executes and optionally tracing every step.
+pub struct Debugger {
+    machine: Machine,
+    breakpoints: BTreeSet<usize>,
+    watches: BTreeSet<usize>,
+    histogram: Vec<u64>,
+    trace: Option<Trace>,
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Self {
+        let histogram = vec![0; machine.program().instructions.len()];
+        Self { machine, breakpoints: BTreeSet::new(), watches: BTreeSet::new(), histogram, trace: None }
+    }
+
+    pub fn machine(&self) -> &Machine {
+        &self.machine
+    }
+
+    pub fn add_breakpoint(&mut self, ip: usize) -> Result<()> {
+        let len = self.histogram.len();
+        ensure!(ip < len, "no instruction {}, the program has {}", ip, len);
+        self.breakpoints.insert(ip);
+        Ok(())
+    }
+
+    /// Returns whether there was a breakpoint at `ip`.
+    pub fn remove_breakpoint(&mut self, ip: usize) -> bool {
+        self.breakpoints.remove(&ip)
+    }
+
+    pub fn watch(&mut self, reg: usize) -> Result<()> {
+        let registers = self.machine.regs.len();
+        ensure!(reg < registers, "no register {}, there are only {}", reg, registers);
+        self.watches.insert(reg);
+        Ok(())
+    }
+
+    /// Traces every following step to `out`, at most `limit` of them.
+    pub fn trace_to(&mut self, out: impl Write + 'static, limit: u64) {
+        self.trace = Some(Trace { out: Box::new(out), limit, written: 0 });
+    }
+
+    /// How many times each instruction has run, indexed by instruction.
+    pub fn histogram(&self) -> &[u64] {
+        &self.histogram
+    }
+
+    /// The `n` most executed instructions with their counts, busiest first.
+    pub fn hottest(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut counts: Vec<(usize, u64)> = self.histogram.iter().copied().enumerate().filter(|&(_, c)| c > 0).collect();
+        counts.sort_by_key(|&(ip, c)| (std::cmp::Reverse(c), ip));
+        counts.truncate(n);
+        counts
+    }
+
+    /// Runs one instruction, stopping early on a halt or a watchpoint.
+    pub fn step(&mut self) -> Result<Stop> {
+        let ip = self.machine.ip();
+        let Some(ins) = self.machine.current().copied() else { return Ok(Stop::Halted) };
+        let mut before = self.machine.regs.clone();
+        if let Some(r) = self.machine.program().ip_reg {
+            before[r] = ip;
+        }
+        self.machine.step()?;
+        self.histogram[ip] += 1;
+        if let Some(trace) = &mut self.trace {
+            trace.record(self.machine.steps(), ip, &before, &ins.to_string(), &self.machine.regs).wrap_err("failed to write the trace")?;
+        }
+        let changed = self.watches.iter().find(|&&r| before[r] != self.machine.regs[r]);
+        Ok(match changed {
+            Some(&reg) => Stop::Watchpoint { ip, reg, old: before[reg], new: self.machine.regs[reg] },
+            None if self.machine.is_halted() => Stop::Halted,
+            None => Stop::Stepped,
+        })
+    }
+
+    /// Runs until something stops it, or for at most `budget` steps. The
+    /// first instruction always runs, so continuing from a breakpoint
+    /// moves past it.
+    pub fn cont(&mut self, budget: Option<u64>) -> Result<Stop> {
+        let mut steps = 0;
+        loop {
+            if budget.is_some_and(|b| steps >= b) {
+                return Ok(Stop::Budget);
+            }
+            match self.step()? {
+                Stop::Stepped => {}
+                stop => return Ok(stop),
+            }
+            steps += 1;
+            let ip = self.machine.ip();
+            if self.breakpoints.contains(&ip) {
+                return Ok(Stop::Breakpoint(ip));
+            }
+        }
+    }
+
+    /// Runs one command of the debugger language and returns what to print:
+    ///
+    /// - `break N`, `delete N`: set or clear a breakpoint at instruction N
+    /// - `watch rN`: stop whenever register N changes
+    /// - `step [K]`: run K instructions (1 by default)
+    /// - `continue [K]`: run to the next stop, at most K steps
+    /// - `regs`: the instruction pointer, registers and next instruction
+    /// - `hot [K]`: the K most executed instructions (10 by default)
+    pub fn command(&mut self, line: &str) -> Result<String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let arg = |i: usize| -> Result<Option<u64>> {
+            words.get(i).map(|w| w.trim_start_matches('r').parse().wrap_err_with(|| format!("`{}` is not a number", w))).transpose()
+        };
+        let required = |i: usize| -> Result<usize> {
+            Ok(arg(i)?.ok_or_else(|| eyre!("`{}` needs an argument", words[0]))? as usize)
+        };
+        let Some(&name) = words.first() else { return Ok(String::new()) };
+        Ok(match name {
+            "b" | "break" => {
+                let ip = required(1)?;
+                self.add_breakpoint(ip)?;
+                format!("breakpoint at {}", ip)
+            }
+            "d" | "delete" => match self.remove_breakpoint(required(1)?) {
+                true => "deleted".to_string(),
+                false => "no breakpoint there".to_string(),
+            },
+            "w" | "watch" => {
+                let reg = required(1)?;
+                self.watch(reg)?;
+                format!("watching r{}", reg)
+            }
+            "s" | "step" => {
+                let mut stop = Stop::Stepped;
+                for _ in 0..arg(1)?.unwrap_or(1) {
+                    stop = self.step()?;
+                    if stop != Stop::Stepped {
+                        break;
+                    }
+                }
+                format!("{}\n{}", stop, self.state())
+            }
+            "c" | "continue" => {
+                let stop = self.cont(arg(1)?)?;
+                format!("{}\n{}", stop, self.state())
+            }
+            "r" | "regs" => self.state(),
+            "hot" => self
+                .hottest(arg(1)?.unwrap_or(10) as usize)
+                .into_iter()
+                .map(|(ip, count)| format!("{:>4}  {:<16}{}", ip, self.machine.program().instructions[ip].to_string(), count))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => bail!("unknown command `{}`", name),
+        })
+    }
+
+    fn state(&self) -> String {
+        let next = match self.machine.current() {
+            Some(ins) => ins.to_string(),
+            None => "halted".to_string(),
+        };
+        format!("ip={} {:?} next: {} ({} steps)", self.machine.ip(), self.machine.regs, next, self.machine.steps())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{elfcode::Program, testing::Shared};
+
+    // Counts r1 up to 5 in a loop at instructions 1-3.
+    const COUNTER: &str = "#ip 0
+seti 0 0 1
+addi 1 1 1
+gtri 1 4 2
+addr 0 2 0
+seti 0 0 0
+seti 7 0 3";
+
+    fn debugger() -> Debugger {
+        Debugger::new(Machine::new(Program::parse(COUNTER).unwrap(), 4).unwrap())
+    }
+
+    #[test]
+    fn test_breakpoints_and_histogram() {
+        let mut dbg = debugger();
+        dbg.add_breakpoint(2).unwrap();
+        assert_eq!(dbg.cont(None).unwrap(), Stop::Breakpoint(2));
+        assert_eq!(dbg.machine().regs[1], 1);
+        assert_eq!(dbg.cont(None).unwrap(), Stop::Breakpoint(2));
+        assert_eq!(dbg.machine().regs[1], 2);
+        assert!(dbg.remove_breakpoint(2));
+        assert_eq!(dbg.cont(None).unwrap(), Stop::Halted);
+        assert_eq!(dbg.histogram(), [1, 5, 5, 5, 4, 1]);
+        assert_eq!(dbg.hottest(2), [(1, 5), (2, 5)]);
+        assert!(dbg.add_breakpoint(6).is_err());
+    }
+
+    #[test]
+    fn test_watchpoint() {
+        let mut dbg = debugger();
+        dbg.watch(2).unwrap();
+        assert_eq!(dbg.cont(None).unwrap(), Stop::Watchpoint { ip: 2, reg: 2, old: 0, new: 1 });
+        assert_eq!(dbg.machine().regs[1], 5);
+        assert!(dbg.watch(4).is_err());
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut dbg = debugger();
+        assert_eq!(dbg.cont(Some(3)).unwrap(), Stop::Budget);
+        assert_eq!(dbg.machine().steps(), 3);
+    }
+
+    #[test]
+    fn test_trace_is_capped() {
+        let mut dbg = debugger();
+        let out = Shared::default();
+        dbg.trace_to(out.clone(), 2);
+        dbg.cont(None).unwrap();
+        let trace = out.text();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines, ["1 ip=0 [0, 0, 0, 0] seti 0 0 1 [0, 0, 0, 0]", "2 ip=1 [1, 0, 0, 0] addi 1 1 1 [1, 1, 0, 0]", "trace capped at 2 steps"]);
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut dbg = debugger();
+        assert_eq!(dbg.command("break 3").unwrap(), "breakpoint at 3");
+        assert_eq!(dbg.command("c").unwrap(), "breakpoint at 3\nip=3 [2, 1, 0, 0] next: addr 0 2 0 (3 steps)");
+        assert_eq!(dbg.command("step 2").unwrap(), "stepped\nip=1 [0, 1, 0, 0] next: addi 1 1 1 (5 steps)");
+        assert_eq!(dbg.command("watch r3").unwrap(), "watching r3");
+        assert!(dbg.command("continue").unwrap().starts_with("breakpoint at 3"));
+        assert_eq!(dbg.command("hot 1").unwrap(), "   1  addi 1 1 1      2");
+        assert!(dbg.command("jump 4").is_err());
+        assert!(dbg.command("break").is_err());
+        assert!(dbg.command("break x").is_err());
+    }
+}
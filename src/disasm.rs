@@ -0,0 +1,381 @@
+use crate::elfcode::{Instruction, Opcode, Program};
+use std::{collections::HashSet, fmt, fmt::Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Reg(usize),
+    Imm(usize),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Reg(r) => write!(f, "r{}", r),
+            Operand::Imm(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// The value an instruction writes to register C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expr {
+    Value(Operand),
+    Binary(&'static str, Operand, Operand),
+}
+
+impl Expr {
+    /// Lifts `ins`, which sits at `idx`. Reading the IP register always
+    /// yields `idx`, so those reads become constants.
+    fn of(ins: &Instruction, idx: usize, ip_reg: Option<usize>) -> Self {
+        use Opcode::*;
+        let (a_reg, b_reg) = ins.op.register_operands();
+        let operand = |value: usize, is_reg: bool| match is_reg {
+            true if Some(value) == ip_reg => Operand::Imm(idx),
+            true => Operand::Reg(value),
+            false => Operand::Imm(value),
+        };
+        let (a, b) = (operand(ins.a, a_reg), operand(ins.b, b_reg));
+        let symbol = match ins.op {
+            Setr | Seti => return Expr::Value(a),
+            Addr | Addi => "+",
+            Mulr | Muli => "*",
+            Banr | Bani => "&",
+            Borr | Bori => "|",
+            Gtir | Gtri | Gtrr => ">",
+            Eqir | Eqri | Eqrr => "==",
+        };
+        Expr::Binary(symbol, a, b).folded()
+    }
+
+    /// Evaluates constant operations. Sums and products that overflow are
+    /// left as they are, since running them fails like in [`Instruction::eval`].
+    fn folded(self) -> Self {
+        let Expr::Binary(symbol, Operand::Imm(a), Operand::Imm(b)) = self else { return self };
+        let value = match symbol {
+            "+" => a.checked_add(b),
+            "*" => a.checked_mul(b),
+            "&" => Some(a & b),
+            "|" => Some(a | b),
+            ">" => Some((a > b) as usize),
+            _ => Some((a == b) as usize),
+        };
+        value.map_or(self, |n| Expr::Value(Operand::Imm(n)))
+    }
+
+    fn constant(&self) -> Option<usize> {
+        match self {
+            Expr::Value(Operand::Imm(n)) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        matches!(self, Expr::Binary(">" | "==", ..))
+    }
+
+    /// `!cond` for a comparison, written without the `!`.
+    fn negated(&self) -> String {
+        match self {
+            Expr::Binary(">", a, b) => format!("{} <= {}", a, b),
+            Expr::Binary("==", a, b) => format!("{} != {}", a, b),
+            e => format!("!({})", e),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Value(v) => write!(f, "{}", v),
+            Expr::Binary(symbol, a, b) => write!(f, "{} {} {}", a, symbol, b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Stmt {
+    Assign(usize, Expr),
+    /// Jump to a known instruction; anything past the end halts.
+    Goto(usize),
+    /// `ip = rX + ip`: jump `rX` instructions past the next one.
+    Skip(usize, usize),
+    /// Any other write to the IP register: jump to `expr + 1`.
+    Computed(Expr),
+    /// Produced when structuring: `if (cond) goto target`, `negate`d or not.
+    If { cond: Expr, negate: bool, target: usize },
+    /// Folded into the statement before it.
+    Nop,
+}
+
+fn lift(program: &Program) -> Vec<Stmt> {
+    program
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(idx, ins)| {
+            let expr = Expr::of(ins, idx, program.ip_reg);
+            if Some(ins.c) != program.ip_reg {
+                return Stmt::Assign(ins.c, expr);
+            }
+            // A target past `usize::MAX` is past the end as well, so it halts.
+            match expr {
+                _ if expr.constant().is_some() => Stmt::Goto(expr.constant().unwrap().saturating_add(1)),
+                Expr::Binary("+", Operand::Reg(r), Operand::Imm(n)) | Expr::Binary("+", Operand::Imm(n), Operand::Reg(r)) => {
+                    match n.checked_add(1) {
+                        Some(base) => Stmt::Skip(r, base),
+                        None => Stmt::Computed(expr),
+                    }
+                }
+                _ => Stmt::Computed(expr),
+            }
+        })
+        .collect()
+}
+
+fn target(len: usize, t: usize) -> String {
+    if t >= len {
+        "halt".to_string()
+    } else {
+        format!("goto {}", t)
+    }
+}
+
+/// One line per instruction: index, the instruction as written, and what it
+/// does with registers named, IP reads resolved and jumps spelled out.
+pub fn listing(program: &Program) -> String {
+    let len = program.instructions.len();
+    let mut out = String::new();
+    if let Some(ip) = program.ip_reg {
+        writeln!(out, "#ip {}  (r{} is the instruction pointer)", ip, ip).unwrap();
+    }
+    for (idx, (ins, stmt)) in program.instructions.iter().zip(lift(program)).enumerate() {
+        let what = match stmt {
+            Stmt::Assign(c, expr) => format!("r{} = {}", c, expr),
+            Stmt::Goto(t) => target(len, t),
+            Stmt::Skip(r, base) => format!("goto {} + r{}", base, r),
+            Stmt::Computed(expr) => format!("goto ({}) + 1", expr),
+            Stmt::If { .. } | Stmt::Nop => unreachable!("lift only produces plain statements"),
+        };
+        writeln!(out, "{:>4}  {:<15} {}", idx, ins.to_string(), what).unwrap();
+    }
+    out
+}
+
+/// Turns `rX = cond; ip = rX + ip; goto T` into `if (!cond) goto T`.
+fn structure_conditions(stmts: &mut [Stmt]) {
+    for j in 1..stmts.len() {
+        let (Stmt::Skip(r, base), Stmt::Assign(x, cond)) = (&stmts[j], &stmts[j - 1]) else { continue };
+        let Some(skipped) = base.checked_add(1) else { continue };
+        if r != x || !cond.is_comparison() {
+            continue;
+        }
+        // The comparison has already overwritten rX, so it can only be
+        // repeated in the `if` when it does not read rX itself.
+        let cond = match cond {
+            Expr::Binary(_, a, b) if *a == Operand::Reg(*x) || *b == Operand::Reg(*x) => {
+                Expr::Binary("==", Operand::Reg(*x), Operand::Imm(1))
+            }
+            cond => *cond,
+        };
+        stmts[j] = Stmt::If { cond, negate: false, target: skipped };
+        let targeted = jump_targets(stmts).contains(&(j + 1));
+        if let (false, Some(&Stmt::Goto(t))) = (targeted, stmts.get(j + 1)) {
+            stmts[j] = Stmt::If { cond, negate: true, target: t };
+            stmts[j + 1] = Stmt::Nop;
+        }
+    }
+}
+
+fn jump_targets(stmts: &[Stmt]) -> HashSet<usize> {
+    stmts
+        .iter()
+        .filter_map(|s| match s {
+            Stmt::Goto(t) | Stmt::If { target: t, .. } => Some(*t),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Backward jumps `(header, end)` that nest cleanly, outermost first.
+fn find_loops(stmts: &[Stmt]) -> Vec<(usize, usize)> {
+    let mut candidates: Vec<(usize, usize)> = stmts
+        .iter()
+        .enumerate()
+        .filter_map(|(j, s)| match s {
+            Stmt::Goto(t) | Stmt::If { target: t, .. } if *t <= j => Some((*t, j)),
+            _ => None,
+        })
+        .collect();
+    candidates.sort_by_key(|&(t, j)| (t, std::cmp::Reverse(j)));
+    let mut loops: Vec<(usize, usize)> = Vec::new();
+    for (t, j) in candidates {
+        let crosses = loops.iter().any(|&(lt, lj)| (lt < t && t <= lj && lj < j) || (t < lt && lt <= j && j < lj));
+        if !crosses && !loops.iter().any(|&(lt, _)| lt == t) {
+            loops.push((t, j));
+        }
+    }
+    loops
+}
+
+struct Printer<'a> {
+    stmts: &'a [Stmt],
+    loops: &'a [(usize, usize)],
+    labels: HashSet<usize>,
+    out: String,
+}
+
+impl Printer<'_> {
+    fn line(&mut self, depth: usize, text: &str) {
+        writeln!(self.out, "{}{}", "    ".repeat(depth), text).unwrap();
+    }
+
+    fn jump(&self, t: usize) -> String {
+        if t >= self.stmts.len() {
+            "return;".to_string()
+        } else {
+            format!("goto L{};", t)
+        }
+    }
+
+    fn emit(&mut self, lo: usize, hi: usize, depth: usize, current: Option<(usize, usize)>) {
+        let mut i = lo;
+        while i < hi {
+            let inner = self.loops.iter().copied().find(|&(t, j)| t == i && j < hi && Some((t, j)) != current);
+            if let Some((t, j)) = inner {
+                if self.labels.contains(&t) {
+                    self.line(depth.saturating_sub(1), &format!("L{}:", t));
+                }
+                match self.stmts[j].clone() {
+                    Stmt::If { cond, negate, .. } => {
+                        self.line(depth, "do {");
+                        self.emit(t, j, depth + 1, Some((t, j)));
+                        let cond = if negate { cond.negated() } else { cond.to_string() };
+                        self.line(depth, &format!("}} while ({});", cond));
+                    }
+                    _ => {
+                        self.line(depth, "while (true) {");
+                        self.emit(t, j, depth + 1, Some((t, j)));
+                        self.line(depth, "}");
+                    }
+                }
+                i = j + 1;
+                continue;
+            }
+            // A loop header's label was printed before the loop itself.
+            if self.labels.contains(&i) && current.map(|(t, _)| t) != Some(i) {
+                self.line(depth.saturating_sub(1), &format!("L{}:", i));
+            }
+            let text = match &self.stmts[i] {
+                Stmt::Nop => None,
+                Stmt::Assign(c, expr) => Some(format!("r{} = {};", c, expr)),
+                Stmt::Goto(t) => Some(self.jump(*t)),
+                Stmt::Skip(r, base) => Some(format!("goto {} + r{};", base, r)),
+                Stmt::Computed(expr) => Some(format!("goto ({}) + 1;", expr)),
+                Stmt::If { cond, negate, target } => {
+                    let cond = if *negate { cond.negated() } else { cond.to_string() };
+                    Some(format!("if ({}) {}", cond, self.jump(*target)))
+                }
+            };
+            if let Some(text) = text {
+                self.line(depth, &text);
+            }
+            i += 1;
+        }
+    }
+}
+
+/// C-like pseudo-code: comparisons feeding a skip become `if`s and backward
+/// jumps become `do { } while` or `while (true)` loops. Jumps that do not
+/// fit that shape stay as `goto`s to labels.
+pub fn pseudo_code(program: &Program) -> String {
+    let mut stmts = lift(program);
+    structure_conditions(&mut stmts);
+    let loops = find_loops(&stmts);
+    let loop_ends: HashSet<usize> = loops.iter().map(|&(_, j)| j).collect();
+    let labels = stmts
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| !loop_ends.contains(j))
+        .filter_map(|(_, s)| match s {
+            Stmt::Goto(t) | Stmt::If { target: t, .. } if *t < stmts.len() => Some(*t),
+            _ => None,
+        })
+        .collect();
+    let mut printer = Printer { stmts: &stmts, loops: &loops, labels, out: String::new() };
+    if let Some(ip) = program.ip_reg {
+        printer.line(0, &format!("// r{} is the instruction pointer", ip));
+    }
+    printer.emit(0, stmts.len(), 1, None);
+    printer.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sums the divisors of r5 into r0, the shape of the day 19 inputs.
+    const DIVISORS: &str = "#ip 4
+seti 1 5 1
+seti 1 2 2
+mulr 1 2 3
+eqrr 3 5 3
+addr 3 4 4
+addi 4 1 4
+addr 1 0 0
+addi 2 1 2
+gtrr 2 5 3
+addr 4 3 4
+seti 1 7 4
+addi 1 1 1
+gtrr 1 5 3
+addr 3 4 4
+seti 0 9 4
+mulr 4 4 4";
+
+    #[test]
+    fn test_listing() {
+        let listing = listing(&Program::parse(DIVISORS).unwrap());
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[0], "#ip 4  (r4 is the instruction pointer)");
+        assert_eq!(lines[3], "   2  mulr 1 2 3      r3 = r1 * r2");
+        assert_eq!(lines[5], "   4  addr 3 4 4      goto 5 + r3");
+        assert_eq!(lines[6], "   5  addi 4 1 4      goto 7");
+        assert_eq!(lines[11], "  10  seti 1 7 4      goto 2");
+        assert_eq!(lines[16], "  15  mulr 4 4 4      halt");
+    }
+
+    #[test]
+    fn test_overflowing_immediates() {
+        let program = Program::parse(&format!("#ip 0\nseti {max} 0 0\naddi 1 {max} 0\nseti 0 0 1\naddi 0 {max} 1", max = usize::MAX)).unwrap();
+        let listing = listing(&program);
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[1], format!("   0  seti {} 0 0 halt", usize::MAX));
+        assert_eq!(lines[2], format!("   1  addi 1 {} 0 goto (r1 + {}) + 1", usize::MAX, usize::MAX));
+        assert_eq!(lines[4], format!("   3  addi 0 {} 1 r1 = 3 + {}", usize::MAX, usize::MAX));
+        assert!(pseudo_code(&program).contains("    return;\n"));
+    }
+
+    #[test]
+    fn test_pseudo_code() {
+        let code = pseudo_code(&Program::parse(DIVISORS).unwrap());
+        let expected = "// r4 is the instruction pointer
+    r1 = 1;
+    do {
+        r2 = 1;
+        do {
+            r3 = r1 * r2;
+            r3 = r3 == r5;
+            if (r3 != 1) goto L7;
+            r0 = r1 + r0;
+        L7:
+            r2 = r2 + 1;
+            r3 = r2 > r5;
+        } while (r2 <= r5);
+        r1 = r1 + 1;
+        r3 = r1 > r5;
+    } while (r1 <= r5);
+    return;
+";
+        assert_eq!(code, expected);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::parse;
+use color_eyre::eyre::{ensure, eyre, Report, Result};
+use std::{fmt, str::FromStr};
+
+/// The sixteen ElfCode opcodes from day 16, shared with day 19.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 16] = [
+        Opcode::Addr,
+        Opcode::Addi,
+        Opcode::Mulr,
+        Opcode::Muli,
+        Opcode::Banr,
+        Opcode::Bani,
+        Opcode::Borr,
+        Opcode::Bori,
+        Opcode::Setr,
+        Opcode::Seti,
+        Opcode::Gtir,
+        Opcode::Gtri,
+        Opcode::Gtrr,
+        Opcode::Eqir,
+        Opcode::Eqri,
+        Opcode::Eqrr,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opcode::Addr => "addr",
+            Opcode::Addi => "addi",
+            Opcode::Mulr => "mulr",
+            Opcode::Muli => "muli",
+            Opcode::Banr => "banr",
+            Opcode::Bani => "bani",
+            Opcode::Borr => "borr",
+            Opcode::Bori => "bori",
+            Opcode::Setr => "setr",
+            Opcode::Seti => "seti",
+            Opcode::Gtir => "gtir",
+            Opcode::Gtri => "gtri",
+            Opcode::Gtrr => "gtrr",
+            Opcode::Eqir => "eqir",
+            Opcode::Eqri => "eqri",
+            Opcode::Eqrr => "eqrr",
+        }
+    }
+
+    /// Whether operands A and B name registers rather than values. C always does.
+    pub fn register_operands(self) -> (bool, bool) {
+        use Opcode::*;
+        match self {
+            Addr | Mulr | Banr | Borr | Gtrr | Eqrr => (true, true),
+            Addi | Muli | Bani | Bori | Setr | Gtri | Eqri => (true, false),
+            Gtir | Eqir => (false, true),
+            Seti => (false, false),
+        }
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Opcode::ALL
+            .into_iter()
+            .find(|op| op.name() == s)
+            .ok_or_else(|| eyre!("unknown opcode `{}`", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub op: Opcode,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl Instruction {
+    pub fn new(op: Opcode, a: usize, b: usize, c: usize) -> Self {
+        Self { op, a, b, c }
+    }
+
+    /// Parses `opcode A B C`, e.g. `addi 4 16 4`.
+    pub fn parse(line: &str) -> Result<Self> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let [name, a, b, c] = parts[..] else {
+            return Err(parse::error_at(line, line.trim(), "expected `opcode A B C`"));
+        };
+        let op = name.parse().map_err(|e| parse::error_at(line, name, e))?;
+        Ok(Self::new(op, parse::number(line, a)?, parse::number(line, b)?, parse::number(line, c)?))
+    }
+
+    /// The registers this instruction reads or writes.
+    pub fn registers(&self) -> impl Iterator<Item = usize> {
+        let (a_reg, b_reg) = self.op.register_operands();
+        [a_reg.then_some(self.a), b_reg.then_some(self.b), Some(self.c)].into_iter().flatten()
+    }
+
+    /// The value this instruction would write to register C, or `None` when
+    /// it reads a register `regs` does not have or the result overflows.
+    pub fn eval(&self, regs: &[usize]) -> Option<usize> {
+        use Opcode::*;
+        let (a, b) = (self.a, self.b);
+        let r = |i: usize| regs.get(i).copied();
+        Some(match self.op {
+            Addr => r(a)?.checked_add(r(b)?)?,
+            Addi => r(a)?.checked_add(b)?,
+            Mulr => r(a)?.checked_mul(r(b)?)?,
+            Muli => r(a)?.checked_mul(b)?,
+            Banr => r(a)? & r(b)?,
+            Bani => r(a)? & b,
+            Borr => r(a)? | r(b)?,
+            Bori => r(a)? | b,
+            Setr => r(a)?,
+            Seti => a,
+            Gtir => (a > r(b)?) as usize,
+            Gtri => (r(a)? > b) as usize,
+            Gtrr => (r(a)? > r(b)?) as usize,
+            Eqir => (a == r(b)?) as usize,
+            Eqri => (r(a)? == b) as usize,
+            Eqrr => (r(a)? == r(b)?) as usize,
+        })
+    }
+
+    /// Executes the instruction on `regs`, returning false (and leaving
+    /// `regs` untouched) when it refers to a register that does not exist
+    /// or its result overflows.
+    pub fn execute(&self, regs: &mut [usize]) -> bool {
+        match (self.eval(regs), self.c < regs.len()) {
+            (Some(value), true) => {
+                regs[self.c] = value;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op, self.a, self.b, self.c)
+    }
+}
+
+/// A list of instructions, optionally with the register the instruction
+/// pointer is bound to (the `#ip N` directive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub ip_reg: Option<usize>,
+    pub instructions: Vec<Instruction>,
+}
+
+impl Program {
+    /// Parses the named format: an optional `#ip N` line, then one
+    /// `opcode A B C` per line.
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim_start();
+        let (first, rest) = trimmed.split_once('\n').unwrap_or((trimmed, ""));
+        let (ip_reg, body) = match first.trim().strip_prefix("#ip") {
+            Some(n) => {
+                let ip = parse::number(first, n).map_err(|e| e.wrap_err(format!("line {}", parse::line_of(input, first))))?;
+                (Some(ip), rest)
+            }
+            None => (None, trimmed),
+        };
+        let instructions = parse::lines_at(input, body, Instruction::parse)?;
+        Ok(Self { ip_reg, instructions })
+    }
+}
+
+impl FromStr for Program {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Program::parse(s)
+    }
+}
+
+/// How a budgeted run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The instruction pointer left the program.
+    Halted,
+    /// The budget ran out first; the machine can be resumed.
+    OutOfBudget,
+}
+
+/// An ElfCode machine: a program, a register file of any size and an
+/// instruction pointer, which may be bound to one of the registers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    program: Program,
+    pub regs: Vec<usize>,
+    ip: usize,
+    steps: u64,
+}
+
+impl Machine {
+    /// Checks that every register the program touches exists.
+    pub fn new(program: Program, registers: usize) -> Result<Self> {
+        if let Some(ip) = program.ip_reg {
+            ensure!(ip < registers, "#ip {} is not a register, there are only {}", ip, registers);
+        }
+        for (idx, ins) in program.instructions.iter().enumerate() {
+            if let Some(r) = ins.registers().find(|&r| r >= registers) {
+                return Err(eyre!("instruction {} `{}` uses register {}, there are only {}", idx, ins, r, registers));
+            }
+        }
+        Ok(Self { program, regs: vec![0; registers], ip: 0, steps: 0 })
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Instructions executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The instruction about to run, `None` once halted.
+    pub fn current(&self) -> Option<&Instruction> {
+        self.program.instructions.get(self.ip)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Runs one instruction. Returns false, doing nothing, once halted, and
+    /// fails when an addition or multiplication overflows a register. The
+    /// machine is left as it was before the failing instruction.
+    pub fn step(&mut self) -> Result<bool> {
+        let Some(&ins) = self.current() else { return Ok(false) };
+        let mut regs = self.regs.clone();
+        if let Some(r) = self.program.ip_reg {
+            regs[r] = self.ip;
+        }
+        ensure!(ins.execute(&mut regs), "instruction {} `{}` overflows with registers {:?}", self.ip, ins, regs);
+        let ip = match self.program.ip_reg {
+            Some(r) => regs[r],
+            None => self.ip,
+        };
+        self.ip = ip.checked_add(1).ok_or_else(|| eyre!("instruction {} `{}` moves the instruction pointer past {}", self.ip, ins, usize::MAX))?;
+        self.regs = regs;
+        self.steps += 1;
+        Ok(true)
+    }
+
+    /// Runs until the program halts. Never returns if it loops forever, see
+    /// [`Machine::run_with_budget`].
+    pub fn run(&mut self) -> Result<()> {
+        while self.step()? {}
+        Ok(())
+    }
+
+    /// Runs at most `budget` more instructions.
+    pub fn run_with_budget(&mut self, budget: u64) -> Result<Outcome> {
+        for _ in 0..budget {
+            if !self.step()? {
+                return Ok(Outcome::Halted);
+            }
+        }
+        Ok(if self.is_halted() {
+            Outcome::Halted
+        } else {
+            Outcome::OutOfBudget
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5";
+
+    #[test]
+    fn test_opcode_names_round_trip() {
+        for op in Opcode::ALL {
+            assert_eq!(op.name().parse::<Opcode>().unwrap(), op);
+        }
+        assert!("movr".parse::<Opcode>().is_err());
+    }
+
+    #[test]
+    fn test_eval() {
+        // The day 16 sample behaves like exactly three opcodes.
+        let regs = [3, 2, 1, 1];
+        let matching: Vec<Opcode> = Opcode::ALL
+            .into_iter()
+            .filter(|&op| Instruction::new(op, 2, 1, 2).eval(&regs) == Some(2))
+            .collect();
+        assert_eq!(matching, [Opcode::Addi, Opcode::Mulr, Opcode::Seti]);
+        assert_eq!(Instruction::new(Opcode::Addr, 4, 0, 0).eval(&regs), None);
+        assert_eq!(Instruction::new(Opcode::Muli, 0, usize::MAX, 0).eval(&regs), None);
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let program = Program::parse(&format!("seti 2 0 0\nmuli 0 {} 0", usize::MAX)).unwrap();
+        let mut machine = Machine::new(program, 1).unwrap();
+        assert!(machine.step().unwrap());
+        let err = machine.step().unwrap_err();
+        assert_eq!(err.to_string(), format!("instruction 1 `muli 0 {} 0` overflows with registers [2]", usize::MAX));
+        assert_eq!((machine.ip(), machine.steps(), &machine.regs[..]), (1, 1, &[2][..]));
+
+        let program = Program::parse(&format!("#ip 0\nseti {} 0 0", usize::MAX)).unwrap();
+        let err = Machine::new(program, 1).unwrap().step().unwrap_err();
+        assert!(err.to_string().contains("moves the instruction pointer past"));
+    }
+
+    #[test]
+    fn test_run_with_ip_register() {
+        let mut machine = Machine::new(EXAMPLE.parse().unwrap(), 6).unwrap();
+        machine.run().unwrap();
+        assert_eq!(machine.regs, [6, 5, 6, 0, 0, 9]);
+        assert_eq!(machine.steps(), 5);
+    }
+
+    #[test]
+    fn test_budget() {
+        let looping = Program::parse("#ip 0\nseti 0 0 1\nseti 0 0 0").unwrap();
+        let mut machine = Machine::new(looping, 2).unwrap();
+        assert_eq!(machine.run_with_budget(1000).unwrap(), Outcome::OutOfBudget);
+        assert_eq!(machine.steps(), 1000);
+        let mut machine = Machine::new(EXAMPLE.parse().unwrap(), 6).unwrap();
+        assert_eq!(machine.run_with_budget(1000).unwrap(), Outcome::Halted);
+    }
+
+    #[test]
+    fn test_register_count_is_checked() {
+        assert!(Machine::new(EXAMPLE.parse().unwrap(), 4).is_err());
+        assert!(Machine::new(Program::parse("#ip 6").unwrap(), 6).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{grid::Grid, json};
+use camino::Utf8PathBuf;
+use color_eyre::eyre::{Result, WrapErr};
+use std::{fs, io::Write};
+
+/// A simulation state that can be drawn as a character map.
+pub trait Snapshot {
+    fn snapshot(&self) -> Grid<char>;
+}
+
+enum Sink {
+    /// Frames one after another, each under a `frame N` line.
+    Ascii(Box<dyn Write>),
+    /// An asciinema v2 recording, `delay` seconds per frame.
+    Asciicast { out: Box<dyn Write>, delay: f64 },
+    /// One binary PPM image per frame, `scale` pixels per tile.
+    Ppm { dir: Utf8PathBuf, scale: usize },
+}
+
+/// Writes simulation frames as they are captured, so long runs never hold
+/// more than one frame in memory.
+pub struct Recorder {
+    sink: Sink,
+    every: usize,
+    calls: usize,
+    written: usize,
+    last_captured: bool,
+    error: Option<color_eyre::Report>,
+}
+
+impl Recorder {
+    fn new(sink: Sink) -> Self {
+        Self { sink, every: 1, calls: 0, written: 0, last_captured: false, error: None }
+    }
+
+    pub fn ascii(out: impl Write + 'static) -> Self {
+        Self::new(Sink::Ascii(Box::new(out)))
+    }
+
+    pub fn asciicast(out: impl Write + 'static, delay: f64) -> Self {
+        Self::new(Sink::Asciicast { out: Box::new(out), delay })
+    }
+
+    /// Writes `frame00000.ppm`, `frame00001.ppm`, ... into `dir`.
+    pub fn ppm(dir: impl Into<Utf8PathBuf>, scale: usize) -> Self {
+        Self::new(Sink::Ppm { dir: dir.into(), scale: scale.max(1) })
+    }
+
+    /// Only keep every `n`th captured state.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Records `state` if it is due. Errors are kept until [`Recorder::finish`]
+    /// so simulations can capture from callbacks that cannot fail.
+    pub fn capture(&mut self, state: &impl Snapshot) {
+        self.calls += 1;
+        self.last_captured = (self.calls - 1).is_multiple_of(self.every);
+        if self.last_captured && self.error.is_none() {
+            if let Err(e) = self.write(&state.snapshot()) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// Records the final state, unless it was the last one captured, and
+    /// returns how many frames were written.
+    pub fn finish(&mut self, state: &impl Snapshot) -> Result<usize> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        if !self.last_captured {
+            self.write(&state.snapshot())?;
+        }
+        match &mut self.sink {
+            Sink::Ascii(out) | Sink::Asciicast { out, .. } => out.flush().wrap_err("failed to write the recording")?,
+            Sink::Ppm { .. } => {}
+        }
+        Ok(self.written)
+    }
+
+    fn write(&mut self, frame: &Grid<char>) -> Result<()> {
+        let n = self.written;
+        match &mut self.sink {
+            Sink::Ascii(out) => write!(out, "frame {}\n{}\n", n, frame).wrap_err("failed to write the recording")?,
+            Sink::Asciicast { out, delay } => {
+                if n == 0 {
+                    let header = format!("{{\"version\": 2, \"width\": {}, \"height\": {}}}", frame.width(), frame.height());
+                    writeln!(out, "{}", header).wrap_err("failed to write the recording")?;
+                }
+                let screen = format!("\x1b[H\x1b[2J{}", frame.to_string().replace('\n', "\r\n"));
+                writeln!(out, "[{:.3}, \"o\", {}]", n as f64 * *delay, json::string(&screen)).wrap_err("failed to write the recording")?;
+            }
+            Sink::Ppm { dir, scale } => {
+                fs::create_dir_all(&*dir).wrap_err_with(|| format!("failed to create {}", dir))?;
+                let path = dir.join(format!("frame{:05}.ppm", n));
+                fs::write(&path, ppm(frame, *scale)).wrap_err_with(|| format!("failed to write {}", path))?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// Colours for the characters the puzzles draw with; anything else is grey.
+fn colour(c: char) -> [u8; 3] {
+    match c {
+        '.' | ' ' => [24, 20, 16],
+        '#' => [110, 90, 70],
+        '~' => [30, 90, 230],
+        '|' => [120, 180, 255],
+        '+' => [255, 255, 255],
+        'G' => [220, 50, 40],
+        'E' => [60, 200, 80],
+        '>' | '<' | '^' | 'v' => [250, 210, 40],
+        'X' => [255, 0, 255],
+        _ => [90, 90, 90],
+    }
+}
+
+/// A binary (P6) PPM image of `frame`, `scale` pixels per tile.
+fn ppm(frame: &Grid<char>, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut out = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    for row in frame.rows() {
+        let line: Vec<u8> = row.iter().flat_map(|&c| [colour(c)].repeat(scale)).flatten().collect();
+        for _ in 0..scale {
+            out.extend_from_slice(&line);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Shared;
+
+    struct Counter(usize);
+
+    impl Snapshot for Counter {
+        fn snapshot(&self) -> Grid<char> {
+            Grid::from_fn(2, 1, |(x, _)| if x == 0 { char::from_digit(self.0 as u32, 10).unwrap() } else { '#' })
+        }
+    }
+
+    #[test]
+    fn test_ascii_every_and_finish() {
+        let out = Shared::default();
+        let mut recorder = Recorder::ascii(out.clone()).every(2);
+        for n in 0..4 {
+            recorder.capture(&Counter(n));
+        }
+        assert_eq!(recorder.finish(&Counter(3)).unwrap(), 3);
+        assert_eq!(out.text(), "frame 0\n0#\n\nframe 1\n2#\n\nframe 2\n3#\n\n");
+    }
+
+    #[test]
+    fn test_asciicast() {
+        let out = Shared::default();
+        let mut recorder = Recorder::asciicast(out.clone(), 0.5);
+        recorder.capture(&Counter(1));
+        recorder.capture(&Counter(2));
+        assert_eq!(recorder.finish(&Counter(2)).unwrap(), 2);
+        let text = out.text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], r#"{"version": 2, "width": 2, "height": 1}"#);
+        assert_eq!(lines[1], r#"[0.000, "o", "\u001b[H\u001b[2J1#\r\n"]"#);
+        assert_eq!(lines[2], r#"[0.500, "o", "\u001b[H\u001b[2J2#\r\n"]"#);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = ppm(&Counter(0).snapshot(), 2);
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 4 * 2 * 3);
+        assert_eq!(image[header.len() + 6..header.len() + 9], colour('#'));
+    }
+}
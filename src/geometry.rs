@@ -0,0 +1,214 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Sub},
+};
+
+/// A position on the plane. `y` grows downwards, like the puzzle maps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The point one step away in `dir`.
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.delta()
+    }
+
+    /// The four orthogonal neighbours in reading order: up, left, right, down.
+    pub fn neighbours4(self) -> [Point; 4] {
+        [self.step(Direction::Up), self.step(Direction::Left), self.step(Direction::Right), self.step(Direction::Down)]
+    }
+
+    /// All eight neighbours, diagonals included, in reading order.
+    pub fn neighbours8(self) -> [Point; 8] {
+        let Point { x, y } = self;
+        [
+            Point::new(x - 1, y - 1),
+            Point::new(x, y - 1),
+            Point::new(x + 1, y - 1),
+            Point::new(x - 1, y),
+            Point::new(x + 1, y),
+            Point::new(x - 1, y + 1),
+            Point::new(x, y + 1),
+            Point::new(x + 1, y + 1),
+        ]
+    }
+
+    /// `(x, y)` as grid indices, or `None` when either is negative.
+    pub fn to_usize(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i32, y as i32)
+    }
+}
+
+/// Reading order: top to bottom, then left to right.
+impl Ord for Point {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+}
+
+/// The smallest rectangle holding a set of points, bounds inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// `None` when `points` is empty.
+    pub fn of(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds { min: first, max: first }, |b, p| Bounds {
+            min: Point::new(b.min.x.min(p.x), b.min.y.min(p.y)),
+            max: Point::new(b.max.x.max(p.x), b.max.y.max(p.y)),
+        }))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.max.y.abs_diff(self.min.y) + 1
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Every point inside, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading_order() {
+        let mut points = vec![Point::new(2, 1), Point::new(0, 2), Point::new(1, 1), Point::new(5, 0)];
+        points.sort();
+        assert_eq!(points, [Point::new(5, 0), Point::new(1, 1), Point::new(2, 1), Point::new(0, 2)]);
+        assert!(Point::new(0, 0).neighbours4().windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(Point::ORIGIN.step(dir).step(dir.reverse()), Point::ORIGIN);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = Bounds::of([Point::new(3, -2), Point::new(-1, 4)]).unwrap();
+        assert_eq!((b.min, b.max), (Point::new(-1, -2), Point::new(3, 4)));
+        assert_eq!((b.width(), b.height()), (5, 7));
+        assert_eq!(b.points().count(), 35);
+        assert!(b.contains(Point::new(0, 0)) && !b.contains(Point::new(4, 0)));
+        assert_eq!(Point::new(1, 1).manhattan(Point::new(-2, 3)), 5);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{geometry::Point, parse};
+use color_eyre::eyre::{ensure, eyre, Result};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A rectangular grid stored row-major in one `Vec`, indexed by `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(&mut f).collect();
+        Self { width, height, cells }
+    }
+
+    /// Builds a grid from rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(eyre!("line {}: expected {} columns, found {}", y + 1, width, rows[y].len()));
+        }
+        let height = rows.len();
+        Ok(Self { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    /// Builds a grid from rows of any length, padding short rows with `fill`.
+    pub fn from_rows_padded(rows: Vec<Vec<T>>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+        Self { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, p: (usize, usize)) -> Option<&T> {
+        self.in_bounds(p).then(|| &self.cells[p.1 * self.width + p.0])
+    }
+
+    pub fn get_mut(&mut self, p: (usize, usize)) -> Option<&mut T> {
+        self.in_bounds(p).then(|| &mut self.cells[p.1 * self.width + p.0])
+    }
+
+    /// Checked lookup by [`Point`]; negative coordinates are out of bounds.
+    pub fn at(&self, p: Point) -> Option<&T> {
+        self.get(p.to_usize()?)
+    }
+
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.get_mut(p.to_usize()?)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+    }
+
+    /// Every position in reading order (top to bottom, left to right).
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The in-bounds orthogonal neighbours of `p`, in reading order: up, left, right, down.
+    pub fn neighbours4(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)].into_iter().filter_map(move |d| self.offset((x, y), d))
+    }
+
+    /// The in-bounds neighbours of `p` including diagonals, in reading order.
+    pub fn neighbours8(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)]
+            .into_iter()
+            .filter_map(move |d| self.offset((x, y), d))
+    }
+
+    fn offset(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let p = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.in_bounds(p).then_some(p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Renders one character per cell, one line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut f));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Grid<char> {
+    /// Reads a rectangular character map, rejecting characters `allowed` does not accept.
+    pub fn parse(input: &str, allowed: impl Fn(char) -> bool, what: &str) -> Result<Self> {
+        let grid = Self::from_rows(parse::char_grid(input, allowed, what)?)?;
+        ensure!(grid.width > 0 && grid.height > 0, "the map is empty");
+        Ok(grid)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside the {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside the {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.at(p).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.at_mut(p).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", p, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse("#.G\n.E#", |c| "#.GE".contains(c), "square").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 'G');
+        assert_eq!(grid.get((1, 1)), Some(&'E'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid[Point::new(1, 1)], 'E');
+        assert_eq!(grid.at(Point::new(-1, 0)), None);
+        assert_eq!(grid.column(2).collect::<String>(), "G#");
+        assert_eq!(grid.to_string(), "#.G\n.E#\n");
+        assert!(Grid::parse("#.\n#", |_| true, "square").is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4((1, 1)).collect::<Vec<_>>(), [(1, 0), (0, 1), (2, 1), (1, 2)]);
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).collect::<Vec<_>>(), [(1, 1), (2, 1), (1, 2)]);
+    }
+}
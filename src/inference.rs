@@ -0,0 +1,206 @@
+use crate::elfcode::Opcode;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// A complete assignment of opcode numbers to opcodes.
+pub type Mapping = BTreeMap<usize, Opcode>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InferenceError {
+    /// No assignment fits. `number` is the opcode number left without
+    /// candidates, when elimination alone got that far.
+    Unsatisfiable { number: Option<usize> },
+    /// At least two assignments fit; they disagree on `number`.
+    Ambiguous { number: usize, options: [Opcode; 2] },
+}
+
+impl fmt::Display for InferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InferenceError::Unsatisfiable { number: Some(n) } => write!(f, "no opcode fits every sample for number {}", n),
+            InferenceError::Unsatisfiable { number: None } => write!(f, "no one-to-one opcode assignment fits the samples"),
+            InferenceError::Ambiguous { number, options: [a, b] } => {
+                write!(f, "the samples do not pin down opcode {}: it could be {} or {}", number, a, b)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InferenceError {}
+
+/// The candidate matrix: which opcodes each opcode number could still be.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Candidates(BTreeMap<usize, BTreeSet<Opcode>>);
+
+impl Candidates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `number` behaved like one of `possible`, narrowing
+    /// whatever earlier observations allowed.
+    pub fn observe(&mut self, number: usize, possible: impl IntoIterator<Item = Opcode>) {
+        let possible: BTreeSet<Opcode> = possible.into_iter().collect();
+        self.0
+            .entry(number)
+            .and_modify(|set| set.retain(|op| possible.contains(op)))
+            .or_insert(possible);
+    }
+
+    pub fn matrix(&self) -> &BTreeMap<usize, BTreeSet<Opcode>> {
+        &self.0
+    }
+
+    pub fn get(&self, number: usize) -> Option<&BTreeSet<Opcode>> {
+        self.0.get(&number)
+    }
+
+    /// Repeatedly removes opcodes pinned to one number from every other
+    /// number, until nothing changes.
+    pub fn eliminate(&mut self) -> Result<(), InferenceError> {
+        loop {
+            if let Some((&number, _)) = self.0.iter().find(|(_, set)| set.is_empty()) {
+                return Err(InferenceError::Unsatisfiable { number: Some(number) });
+            }
+            let pinned: Vec<(usize, Opcode)> = self
+                .0
+                .iter()
+                .filter(|(_, set)| set.len() == 1)
+                .map(|(&n, set)| (n, *set.first().unwrap()))
+                .collect();
+            let mut changed = false;
+            for (n, op) in pinned {
+                for (_, set) in self.0.iter_mut().filter(|(&other, _)| other != n) {
+                    changed |= set.remove(&op);
+                }
+            }
+            if !changed {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Elimination first, then a backtracking search over what is left.
+    /// Succeeds only when exactly one one-to-one assignment fits.
+    pub fn solve(&self) -> Result<Mapping, InferenceError> {
+        let mut narrowed = self.clone();
+        narrowed.eliminate()?;
+        let mut solutions = Vec::new();
+        narrowed.search(&mut Mapping::new(), &mut solutions);
+        match &solutions[..] {
+            [] => Err(InferenceError::Unsatisfiable { number: None }),
+            [only] => Ok(only.clone()),
+            [a, b, ..] => {
+                let (&number, &first) = a.iter().find(|&(n, op)| b[n] != *op).unwrap();
+                Err(InferenceError::Ambiguous { number, options: [first, b[&number]] })
+            }
+        }
+    }
+
+    /// Collects up to two solutions, which is enough to tell unique from
+    /// ambiguous. Tries the most constrained number first, and only goes
+    /// down branches that can still be completed, so it never wanders
+    /// through the factorially many dead ends of a pigeonhole.
+    fn search(&self, partial: &mut Mapping, solutions: &mut Vec<Mapping>) {
+        if solutions.len() >= 2 || !self.completable(partial) {
+            return;
+        }
+        let next = self.0.iter().filter(|(n, _)| !partial.contains_key(n)).min_by_key(|(_, set)| set.len());
+        let Some((&number, set)) = next else {
+            solutions.push(partial.clone());
+            return;
+        };
+        for &op in set {
+            if partial.values().all(|&used| used != op) {
+                partial.insert(number, op);
+                self.search(partial, solutions);
+                partial.remove(&number);
+            }
+        }
+    }
+
+    /// Whether the numbers `partial` leaves open can each still get a
+    /// different unused opcode: a bipartite matching that covers them all.
+    fn completable(&self, partial: &Mapping) -> bool {
+        let mut owner = BTreeMap::new();
+        self.0
+            .keys()
+            .filter(|n| !partial.contains_key(n))
+            .all(|&number| self.augment(number, partial, &mut owner, &mut BTreeSet::new()))
+    }
+
+    /// Finds `number` an opcode, moving earlier numbers along to other
+    /// candidates of theirs where needed.
+    fn augment(&self, number: usize, partial: &Mapping, owner: &mut BTreeMap<Opcode, usize>, seen: &mut BTreeSet<Opcode>) -> bool {
+        for &op in &self.0[&number] {
+            if partial.values().any(|&used| used == op) || !seen.insert(op) {
+                continue;
+            }
+            if owner.get(&op).copied().is_none_or(|other| self.augment(other, partial, owner, seen)) {
+                owner.insert(op, number);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Opcode::*;
+
+    #[test]
+    fn test_elimination_solves_a_chain() {
+        let mut candidates = Candidates::new();
+        candidates.observe(0, [Addr, Addi, Mulr]);
+        candidates.observe(0, [Addr, Addi]);
+        candidates.observe(1, [Addi]);
+        candidates.observe(2, [Addr, Addi, Mulr]);
+        assert_eq!(candidates.get(0).unwrap().len(), 2);
+        assert_eq!(candidates.solve().unwrap(), Mapping::from([(0, Addr), (1, Addi), (2, Mulr)]));
+    }
+
+    #[test]
+    fn test_search_when_elimination_stalls() {
+        // Nothing is pinned, but 0 and 1 need both of addr and addi between
+        // them, so only 2 can be mulr. The search still finds 0 and 1 swap.
+        let mut candidates = Candidates::new();
+        candidates.observe(0, [Addr, Addi]);
+        candidates.observe(1, [Addr, Addi]);
+        candidates.observe(2, [Addr, Addi, Mulr]);
+        let mut stuck = candidates.clone();
+        stuck.eliminate().unwrap();
+        assert_eq!(stuck, candidates);
+        assert_eq!(candidates.solve(), Err(InferenceError::Ambiguous { number: 0, options: [Addr, Addi] }));
+        candidates.observe(1, [Addi]);
+        assert_eq!(candidates.solve().unwrap(), Mapping::from([(0, Addr), (1, Addi), (2, Mulr)]));
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        let mut candidates = Candidates::new();
+        candidates.observe(0, [Addr, Addi]);
+        candidates.observe(0, [Mulr]);
+        assert_eq!(candidates.solve(), Err(InferenceError::Unsatisfiable { number: Some(0) }));
+
+        let mut candidates = Candidates::new();
+        candidates.observe(0, [Addr, Addi]);
+        candidates.observe(1, [Addr, Addi]);
+        candidates.observe(2, [Addr, Addi]);
+        assert_eq!(candidates.solve(), Err(InferenceError::Unsatisfiable { number: None }));
+    }
+
+    #[test]
+    fn test_pigeonhole_fails_fast() {
+        // Sixteen numbers sharing fifteen opcodes: elimination pins nothing
+        // and a blind search would try every partial assignment first.
+        let mut candidates = Candidates::new();
+        for number in 0..16 {
+            candidates.observe(number, Opcode::ALL[..15].iter().copied());
+        }
+        assert_eq!(candidates.solve(), Err(InferenceError::Unsatisfiable { number: None }));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::days::Day;
+use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::io;
+
+pub const DEFAULT_DIR: &str = "inputs";
+
+/// Where a day's puzzle input should be read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(Utf8PathBuf),
+    Stdin,
+    /// A directory following the `dayNN.txt` naming convention.
+    Dir(Utf8PathBuf),
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a file path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(path.into()),
+        }
+    }
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Dir(DEFAULT_DIR.into())
+    }
+}
+
+pub fn convention_path(dir: &Utf8Path, day: u8) -> Utf8PathBuf {
+    dir.join(format!("day{:02}.txt", day))
+}
+
+/// Reads the input for `day`, falling back to the embedded copy when the
+/// convention file is missing. Blank input counts as missing, so no
+/// puzzle is ever solved for an empty placeholder file.
+pub fn load(day: &Day, source: &Source) -> Result<String> {
+    let (input, origin) = read(day, source)?;
+    if input.trim().is_empty() {
+        return Err(eyre!("no input for day {}: {} is empty", day.day, origin));
+    }
+    Ok(input)
+}
+
+/// The input, and where it came from for error messages.
+fn read(day: &Day, source: &Source) -> Result<(String, String)> {
+    match source {
+        Source::File(path) => Ok((read_file(path)?, path.to_string())),
+        Source::Stdin => Ok((io::read_to_string(io::stdin()).wrap_err("failed to read input from stdin")?, "stdin".to_string())),
+        Source::Dir(dir) => {
+            let path = convention_path(dir, day.day);
+            match (path.exists(), day.input) {
+                (false, Some(embedded)) => Ok((embedded.to_string(), "the embedded input".to_string())),
+                (false, None) => Err(eyre!("no input for day {}: {} does not exist", day.day, path)),
+                (true, _) => Ok((read_file(&path)?, path.to_string())),
+            }
+        }
+    }
+}
+
+fn read_file(path: &Utf8Path) -> Result<String> {
+    std::fs::read_to_string(path).wrap_err_with(|| format!("failed to read {}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_convention_path() {
+        assert_eq!(convention_path("inputs".into(), 7), Utf8PathBuf::from("inputs/day07.txt"));
+    }
+
+    #[test]
+    fn test_missing_dir() {
+        let day = days::get(7).unwrap();
+        let source = Source::Dir("does/not/exist".into());
+        assert_eq!(load(day, &source).is_ok(), day.input.is_some_and(|input| !input.trim().is_empty()));
+    }
+
+    #[test]
+    fn test_blank_input_is_missing() {
+        let path = Utf8PathBuf::try_from(std::env::temp_dir()).unwrap().join("aoc2018-blank-input.txt");
+        std::fs::write(&path, " \n\n").unwrap();
+        let err = load(days::get(7).unwrap(), &Source::File(path.clone())).unwrap_err();
+        assert_eq!(err.to_string(), format!("no input for day 7: {} is empty", path));
+    }
+
+    #[test]
+    fn test_blank_embedded_input() {
+        let day = Day { input: Some("\n"), ..*days::get(7).unwrap() };
+        let err = load(&day, &Source::Dir("does/not/exist".into())).unwrap_err();
+        assert_eq!(err.to_string(), "no input for day 7: the embedded input is empty");
+    }
+}
@@ -0,0 +1,188 @@
+use color_eyre::eyre::{eyre, Report, Result, WrapErr};
+use std::{fmt::Display, str::FromStr};
+
+/// Column (0-based, in chars) where `span` starts inside `line`. `span` must be
+/// a subslice of `line`; anything else points at the start of the line.
+fn column_of(line: &str, span: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let at = span.as_ptr() as usize;
+    if at < start || at + span.len() > start + line.len() {
+        return 0;
+    }
+    line[..at - start].chars().count()
+}
+
+/// An error pointing at `span` within `line`, rendered as
+///
+/// ```text
+/// column 13: invalid number `5x4`
+///     y=13, x=498..5x4
+///                  ^^^
+/// ```
+///
+/// Long lines (day 5's polymer is a single 50k char line) are cut down to a
+/// window around the span.
+pub fn error_at(line: &str, span: &str, msg: impl Display) -> Report {
+    const CONTEXT: usize = 30;
+    let column = column_of(line, span);
+    let width = span.chars().count().clamp(1, CONTEXT);
+    let skip = column.saturating_sub(CONTEXT);
+    let take = CONTEXT * 2 + width;
+    let mut shown: String = line.chars().skip(skip).take(take).collect();
+    let mut pad = column - skip;
+    if skip > 0 {
+        shown.insert_str(0, "...");
+        pad += 3;
+    }
+    if skip + take < line.chars().count() {
+        shown.push_str("...");
+    }
+    eyre!(
+        "column {}: {}\n    {}\n    {}{}",
+        column + 1,
+        msg,
+        shown,
+        " ".repeat(pad),
+        "^".repeat(width)
+    )
+}
+
+/// Parses `span` (a subslice of `line`) as a number.
+pub fn number<T>(line: &str, span: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let trimmed = span.trim();
+    trimmed
+        .parse()
+        .map_err(|e| error_at(line, trimmed, format!("invalid number `{}`: {}", trimmed, e)))
+}
+
+pub fn split_once<'a>(line: &str, span: &'a str, pat: &str) -> Result<(&'a str, &'a str)> {
+    span.split_once(pat)
+        .ok_or_else(|| error_at(line, span, format!("expected `{}`", pat)))
+}
+
+pub fn strip_prefix<'a>(line: &str, span: &'a str, prefix: &str) -> Result<&'a str> {
+    span.strip_prefix(prefix)
+        .ok_or_else(|| error_at(line, span, format!("expected `{}`", prefix)))
+}
+
+pub fn strip_suffix<'a>(line: &str, span: &'a str, suffix: &str) -> Result<&'a str> {
+    span.strip_suffix(suffix)
+        .ok_or_else(|| error_at(line, span, format!("expected `{}` at the end", suffix)))
+}
+
+/// Turns a nom result for `line` into a report pointing at where nom gave up.
+pub fn nom<'a, T>(line: &'a str, result: nom::IResult<&'a str, T>, expected: &str) -> Result<T> {
+    match result {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let at = e.input.chars().next().map_or(0, char::len_utf8);
+            Err(error_at(line, &e.input[..at], format!("expected {}", expected)))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(error_at(line, "", format!("expected {}", expected))),
+    }
+}
+
+/// 1-based number of the line `span` starts on. `span` must be a subslice of `input`.
+pub fn line_of(input: &str, span: &str) -> usize {
+    let offset = (span.as_ptr() as usize).saturating_sub(input.as_ptr() as usize).min(input.len());
+    input[..offset].matches('\n').count() + 1
+}
+
+/// Runs `f` on every non-blank line, tagging failures with the 1-based line number.
+pub fn lines<'a, T>(input: &'a str, f: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    lines_at(input, input, f)
+}
+
+/// Like [`lines`] for a `section` of `input`, numbering lines by their
+/// position in the whole of `input`.
+pub fn lines_at<'a, T>(input: &str, section: &'a str, mut f: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    let first = line_of(input, section);
+    section
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| f(line).wrap_err_with(|| format!("line {}", first + idx)))
+        .collect()
+}
+
+/// Splits `input` into paragraphs separated by one or more blank lines.
+/// Works the same for LF and CRLF files, and lines holding only whitespace
+/// count as blank. Every block is a subslice of `input` without its trailing
+/// line ending, so [`line_of`] and [`lines_at`] still number lines correctly.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end();
+        match (content.trim_start().is_empty(), current) {
+            (true, Some((start, end))) => {
+                blocks.push(&input[start..end]);
+                current = None;
+            }
+            (true, None) => {}
+            (false, Some((start, _))) => current = Some((start, offset + content.len())),
+            (false, None) => current = Some((offset, offset + content.len())),
+        }
+        offset += line.len();
+    }
+    if let Some((start, end)) = current {
+        blocks.push(&input[start..end]);
+    }
+    blocks
+}
+
+/// Reads a character map, rejecting any character `allowed` does not accept.
+/// Unlike [`lines`], blank lines are kept so row numbers stay aligned.
+pub fn char_grid(input: &str, allowed: impl Fn(char) -> bool, what: &str) -> Result<Vec<Vec<char>>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| match line.find(|c| !allowed(c)) {
+            Some(at) => Err(error_at(line, &line[at..at + 1], format!("unknown {}", what)))
+                .wrap_err_with(|| format!("line {}", idx + 1)),
+            None => Ok(line.chars().collect()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_points_at_span() {
+        let input = "x=495, y=2..7\ny=13, x=498..5x4";
+        let err = lines(input, |line| {
+            let (_, range) = split_once(line, line, "..")?;
+            number::<usize>(line, range)
+        })
+        .unwrap_err();
+        let msg = format!("{:#}", err);
+        assert!(msg.starts_with("line 2: column 14: invalid number `5x4`"));
+        assert!(msg.ends_with("\n    y=13, x=498..5x4\n                 ^^^"));
+    }
+
+    #[test]
+    fn test_blocks_ignore_line_endings() {
+        let expected = vec!["a\nb", "c", "d"];
+        assert_eq!(blocks("a\nb\n\nc\n\n\n\nd\n"), expected);
+        let input = "\r\na\r\nb\r\n \t\r\nc\r\n\r\n\r\n\r\nd";
+        let crlf = blocks(input);
+        assert_eq!(crlf.iter().map(|b| b.replace("\r\n", "\n")).collect::<Vec<_>>(), expected);
+        assert_eq!(line_of(input, crlf[1]), 5);
+    }
+
+    #[test]
+    fn test_long_line_is_windowed() {
+        let line = format!("{}#{}", "a".repeat(100), "b".repeat(100));
+        let msg = error_at(&line, &line[100..101], "bad unit").to_string();
+        let shown: Vec<&str> = msg.lines().collect();
+        assert_eq!(shown[1].trim_start(), format!("...{}#{}...", "a".repeat(30), "b".repeat(30)));
+        assert_eq!(shown[2].find('^'), shown[1].find('#'));
+    }
+}
@@ -0,0 +1,95 @@
+use color_eyre::eyre::Result;
+use std::fmt;
+
+/// A puzzle answer, rendered in the format the puzzle page expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// `x,y`
+    Point(usize, usize),
+    /// `x,y,size`
+    Square(usize, usize, usize),
+    Unsolved,
+    /// Unsolved, with the reason.
+    Failed(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Point(x, y) => write!(f, "{},{}", x, y),
+            Answer::Square(x, y, size) => write!(f, "{},{},{}", x, y, size),
+            Answer::Unsolved => write!(f, "unsolved"),
+            Answer::Failed(reason) => write!(f, "unsolved: {}", reason),
+        }
+    }
+}
+
+/// Integers too large for `i64` keep their digits as text rather than
+/// wrapping around to a negative number.
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Int(n),
+                    Err(_) => Answer::Text(n.to_string()),
+                }
+            }
+        })*
+    };
+}
+
+impl_from_int!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<(usize, usize)> for Answer {
+    fn from((x, y): (usize, usize)) -> Self {
+        Answer::Point(x, y)
+    }
+}
+
+impl From<(usize, usize, usize)> for Answer {
+    fn from((x, y, size): (usize, usize, usize)) -> Self {
+        Answer::Square(x, y, size)
+    }
+}
+
+/// One day's puzzle. `parse` turns the raw input into the day's state and
+/// both parts are solved from that state.
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self>
+    where
+        Self: Sized;
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_ints_do_not_wrap() {
+        assert_eq!(Answer::from(-5i32), Answer::Int(-5));
+        assert_eq!(Answer::from(i64::MAX as u64), Answer::Int(i64::MAX));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Wall-clock statistics for one phase (parse, part 1 or part 2) of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub runs: u32,
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+/// Calls `f` `runs` times (at least once) and returns the last result with its timings.
+pub fn measure<T>(runs: u32, mut f: impl FnMut() -> T) -> (T, Timing) {
+    let runs = runs.max(1);
+    let (mut min, mut max, mut total) = (Duration::MAX, Duration::ZERO, Duration::ZERO);
+    let mut last = None;
+    for _ in 0..runs {
+        let start = Instant::now();
+        let value = f();
+        let elapsed = start.elapsed();
+        min = min.min(elapsed);
+        max = max.max(elapsed);
+        total += elapsed;
+        last = Some(value);
+    }
+    let timing = Timing { runs, min, mean: total / runs, max };
+    (last.unwrap(), timing)
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.runs == 1 {
+            write!(f, "{:.2?}", self.mean)
+        } else {
+            write!(f, "mean {:.2?}, min {:.2?}, max {:.2?} over {} runs", self.mean, self.min, self.max, self.runs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (last, timing) = measure(5, || {
+            calls += 1;
+            calls
+        });
+        assert_eq!((last, calls, timing.runs), (5, 5, 5));
+        assert!(timing.min <= timing.mean && timing.mean <= timing.max);
+        assert_eq!(measure(0, || ()).1.runs, 1);
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{frames::Snapshot, grid::Grid, parse};
+use color_eyre::eyre::{ensure, Result};
+use std::{fmt, ops::RangeInclusive};
+
+/// One square of ground, a byte each so deep scans stay small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Sand,
+    Clay,
+    Spring,
+    /// Water that passed through and drained away.
+    Flowing,
+    /// Water that came to rest.
+    Settled,
+}
+
+impl Tile {
+    pub fn symbol(self) -> char {
+        match self {
+            Tile::Sand => '.',
+            Tile::Clay => '#',
+            Tile::Spring => '+',
+            Tile::Flowing => '|',
+            Tile::Settled => '~',
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Pending work in [`Reservoir::fill`].
+#[derive(Debug, Clone, Copy)]
+enum Work {
+    /// Water arrives at `(x, top)` and falls.
+    Fall { x: usize, top: usize },
+    /// Water resting on row `y` at `x` spreads sideways; once the row
+    /// settles, the row above spreads too, up to `top`.
+    Spread { x: usize, y: usize, top: usize },
+}
+
+/// Where spreading along a row stopped on one side.
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    Wall(usize),
+    /// Sand below this column: water has to fall there first.
+    Drop(usize),
+    /// Flowing water below: it drains away.
+    Drain,
+}
+
+/// One line of the scan: a run of clay, one of the ranges a single value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vein {
+    pub x: RangeInclusive<usize>,
+    pub y: RangeInclusive<usize>,
+}
+
+fn coordinate<'a>(line: &'a str, part: &'a str) -> Result<(&'a str, RangeInclusive<usize>)> {
+    let (axis, value) = parse::split_once(line, part, "=")?;
+    if axis != "x" && axis != "y" {
+        return Err(parse::error_at(line, axis, "expected `x` or `y`"));
+    }
+    let range = match value.split_once("..") {
+        Some((start, end)) => parse::number(line, start)?..=parse::number(line, end)?,
+        None => {
+            let n = parse::number(line, value)?;
+            n..=n
+        }
+    };
+    Ok((axis, range))
+}
+
+/// Parses `x=495, y=2..7` or `y=7, x=495..501` lines.
+pub fn parse_veins(input: &str) -> Result<Vec<Vein>> {
+    parse::lines(input, |line| {
+        let (a, b) = parse::split_once(line, line.trim(), ", ")?;
+        match (coordinate(line, a)?, coordinate(line, b)?) {
+            (("x", x), ("y", y)) | (("y", y), ("x", x)) => Ok(Vein { x, y }),
+            _ => Err(parse::error_at(line, line.trim(), "expected one `x` and one `y`")),
+        }
+    })
+}
+
+/// The ground under the spring. The grid spans the clay's x range plus a
+/// column either side for water to spill over, and from two rows above the
+/// highest clay (room for the spring and for water to spill over the top)
+/// down to the lowest; column 1 is x = `min_x`. Nothing above the clay
+/// stops the water, so the spring is drawn on row 0 however far up it
+/// really is.
+#[derive(Debug, Clone)]
+pub struct Reservoir {
+    grid: Grid<Tile>,
+    min_x: usize,
+    min_y: usize,
+}
+
+impl Reservoir {
+    /// Where the water comes from.
+    pub const SPRING: (usize, usize) = (500, 0);
+
+    pub fn new(veins: &[Vein]) -> Result<Self> {
+        ensure!(!veins.is_empty(), "the scan has no clay");
+        let min_x = veins.iter().map(|v| *v.x.start()).min().unwrap().min(Self::SPRING.0);
+        let max_x = veins.iter().map(|v| *v.x.end()).max().unwrap().max(Self::SPRING.0);
+        let min_y = veins.iter().map(|v| *v.y.start()).min().unwrap();
+        let max_y = veins.iter().map(|v| *v.y.end()).max().unwrap();
+        ensure!(min_y > Self::SPRING.1, "clay at y={} is not below the spring", min_y);
+        let column = |x: usize| x + 1 - min_x;
+        let row = |y: usize| y - Self::top(min_y);
+        let mut grid = Grid::new(column(max_x) + 2, row(max_y) + 1, Tile::Sand);
+        for vein in veins {
+            for y in vein.y.clone() {
+                for x in vein.x.clone() {
+                    grid[(column(x), row(y))] = Tile::Clay;
+                }
+            }
+        }
+        grid[(column(Self::SPRING.0), 0)] = Tile::Spring;
+        Ok(Self { grid, min_x, min_y })
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::new(&parse_veins(input)?)
+    }
+
+    /// The y of the grid's first row.
+    fn top(min_y: usize) -> usize {
+        min_y.saturating_sub(2)
+    }
+
+    /// The grid row of the lowest clay.
+    fn bottom(&self) -> usize {
+        self.grid.height() - 1
+    }
+
+    /// Lets the spring run until nothing changes. Works off an explicit
+    /// stack rather than recursing, so any depth of cascade is fine.
+    pub fn fill(&mut self) {
+        self.fill_with(|_| {});
+    }
+
+    /// [`Reservoir::fill`], calling `observe` after every fall and spread.
+    pub fn fill_with(&mut self, mut observe: impl FnMut(&Self)) {
+        let x = Self::SPRING.0 + 1 - self.min_x;
+        // With clay right under the spring, the water spreads from the spring's own row.
+        let mut work = match self.grid[(x, 1)] {
+            Tile::Sand => vec![Work::Fall { x, top: 1 }],
+            _ => vec![Work::Spread { x, y: 0, top: 0 }],
+        };
+        while let Some(item) = work.pop() {
+            observe(self);
+            match item {
+                Work::Fall { x, top } => {
+                    if let Some(y) = self.fall(x, top) {
+                        work.push(Work::Spread { x, y, top });
+                    }
+                }
+                Work::Spread { x, y, top } => match (self.spread(x, y, -1), self.spread(x, y, 1)) {
+                    // Come back to this row once the water below the edge has settled or drained.
+                    (Edge::Drop(edge), _) | (_, Edge::Drop(edge)) => {
+                        work.push(Work::Spread { x, y, top });
+                        work.push(Work::Fall { x: edge, top: y + 1 });
+                    }
+                    (Edge::Wall(left), Edge::Wall(right)) => {
+                        for x in left..=right {
+                            self.grid[(x, y)] = Tile::Settled;
+                        }
+                        if y > top {
+                            work.push(Work::Spread { x, y: y - 1, top });
+                        }
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    /// Marks water falling from `(x, top)`. Returns the row where it comes
+    /// to rest on clay or settled water, `None` when it drains away.
+    fn fall(&mut self, x: usize, top: usize) -> Option<usize> {
+        let mut y = top;
+        loop {
+            self.grid[(x, y)] = Tile::Flowing;
+            if y == self.bottom() {
+                return None;
+            }
+            match self.grid[(x, y + 1)] {
+                Tile::Sand => y += 1,
+                Tile::Flowing => return None,
+                _ => return Some(y),
+            }
+        }
+    }
+
+    /// Marks water spreading along row `y` from `x` in direction `dir`.
+    fn spread(&mut self, mut x: usize, y: usize, dir: isize) -> Edge {
+        loop {
+            match self.grid[(x, y + 1)] {
+                Tile::Sand => return Edge::Drop(x),
+                Tile::Flowing => return Edge::Drain,
+                _ => {}
+            }
+            let next = x.checked_add_signed(dir).unwrap();
+            if self.grid[(next, y)] == Tile::Clay {
+                return Edge::Wall(x);
+            }
+            self.grid[(next, y)] = Tile::Flowing;
+            x = next;
+        }
+    }
+
+    /// Tiles holding `tile` between the highest and lowest clay; water
+    /// above or below the scanned range does not count.
+    fn count(&self, tile: Tile) -> usize {
+        (self.min_y - Self::top(self.min_y)..=self.bottom()).flat_map(|y| self.grid.row(y)).filter(|&&t| t == tile).count()
+    }
+
+    pub fn settled(&self) -> usize {
+        self.count(Tile::Settled)
+    }
+
+    pub fn flowing(&self) -> usize {
+        self.count(Tile::Flowing)
+    }
+
+    /// Every tile water reached, settled or not.
+    pub fn wet(&self) -> usize {
+        self.settled() + self.flowing()
+    }
+}
+
+impl Snapshot for Reservoir {
+    fn snapshot(&self) -> Grid<char> {
+        self.grid.map(|t| t.symbol())
+    }
+}
+
+impl fmt::Display for Reservoir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504";
+
+    #[test]
+    fn test_example() {
+        let mut reservoir = Reservoir::parse(EXAMPLE).unwrap();
+        reservoir.fill();
+        assert_eq!((reservoir.wet(), reservoir.settled(), reservoir.flowing()), (57, 29, 28));
+        let expected = "\
+......+.......
+......|.....#.
+.#..#||||...#.
+.#..#~~#|.....
+.#..#~~#|.....
+.#~~~~~#|.....
+.#~~~~~#|.....
+.#######|.....
+........|.....
+...|||||||||..
+...|#~~~~~#|..
+...|#~~~~~#|..
+...|#~~~~~#|..
+...|#######|..";
+        assert_eq!(reservoir.to_string().trim_end(), expected);
+    }
+
+    #[test]
+    fn test_clipping() {
+        // The spring is at y = 0 but counting starts at the highest clay.
+        let mut reservoir = Reservoir::parse("x=499, y=5..6\nx=501, y=5..6\ny=6, x=499..501").unwrap();
+        reservoir.fill();
+        assert_eq!((reservoir.settled(), reservoir.flowing()), (1, 4));
+    }
+
+    #[test]
+    fn test_arbitrary_x() {
+        // Far from the spring on both sides, and right at x = 0.
+        let mut reservoir = Reservoir::parse("y=3, x=0..1000\nx=0, y=1..3\nx=1000, y=1..3").unwrap();
+        reservoir.fill();
+        assert_eq!(reservoir.settled(), 999 * 2);
+    }
+
+    #[test]
+    fn test_rows_start_at_the_clay() {
+        let mut reservoir = Reservoir::parse("x=499, y=100000..100001\nx=501, y=100000..100001\ny=100001, x=499..501").unwrap();
+        reservoir.fill();
+        assert_eq!((reservoir.settled(), reservoir.flowing()), (1, 4));
+        assert_eq!(reservoir.to_string().trim_end(), "..+..\n|||||\n|#~#|\n|###|");
+    }
+
+    #[test]
+    fn test_clay_under_the_spring() {
+        let mut reservoir = Reservoir::parse("x=500, y=1..3\nx=498, y=3..5").unwrap();
+        reservoir.fill();
+        assert_eq!((reservoir.wet(), reservoir.settled()), (10, 0));
+        assert_eq!(reservoir.to_string().trim_end(), "..|+|\n..|#|\n..|#|\n.#|#|\n.#|.|\n.#|.|");
+    }
+
+    /// `n` ledges, each walled on one side so the water runs off the
+    /// other onto the next, zigzagging down to y = 5n.
+    fn cascade(n: usize) -> Vec<Vein> {
+        (0..n)
+            .flat_map(|i| {
+                let y = 5 * (i + 1);
+                let (ledge, wall) = if i % 2 == 0 { (500..=504, 499) } else { (501..=505, 506) };
+                [Vein { x: ledge, y: y..=y }, Vein { x: wall..=wall, y: y - 1..=y - 1 }]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_deep_cascade() {
+        // Six wet tiles above each ledge, four falling past it to the next
+        // and one below the last. Recursing once per ledge would overflow
+        // the stack long before y = 100000.
+        for n in [1, 2, 3, 20_000] {
+            let mut reservoir = Reservoir::new(&cascade(n)).unwrap();
+            reservoir.fill();
+            assert_eq!((reservoir.wet(), reservoir.settled()), (10 * n - 3, 0), "{} ledges", n);
+        }
+    }
+
+    #[test]
+    fn test_rejects() {
+        assert!(Reservoir::parse("").is_err());
+        assert!(Reservoir::parse("x=1, y=0..3").is_err());
+        assert!(Reservoir::parse("x=1, x=0..3").is_err());
+        assert!(Reservoir::parse("z=1, y=0..3").is_err());
+    }
+}